@@ -3,10 +3,11 @@ use std::{fmt, io};
 use crate::{
     bimap::BiMap,
     parse,
-    symbols::{self, CellRole, Chars},
+    piece::Piece,
+    symbols::{self, CellRole, Chars, Player},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Anfield {
     pub width: usize,
     pub height: usize,
@@ -20,13 +21,13 @@ pub struct Anfield {
 
 impl fmt::Display for Anfield {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Anfield {} {}\n", self.width, self.height)?;
+        writeln!(f, "Anfield {} {}", self.width, self.height)?;
 
         write!(f, "    ")?;
         for x in 0..self.width {
             write!(f, "{}", x % 10)?;
         }
-        write!(f, "\n")?;
+        writeln!(f)?;
 
         for y in 0..self.height {
             write!(f, "{:03} ", y)?;
@@ -40,7 +41,7 @@ impl fmt::Display for Anfield {
                         .expect("Invalid cell found in BiMap of Anfield")
                 )?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
 
         write!(
@@ -73,6 +74,20 @@ impl Anfield {
         }
     }
 
+    // The same board from the other player's point of view.
+    pub fn swapped(&self) -> Self {
+        let opponent_id = if self.own_char == symbols::P1_CHAR {
+            2
+        } else {
+            1
+        };
+        let mut anfield = Anfield::new(opponent_id);
+        anfield.width = self.width;
+        anfield.height = self.height;
+        anfield.cells = self.cells.iter().map(|cell| cell.swapped()).collect();
+        anfield
+    }
+
     pub fn get_cell_role(&self, x: usize, y: usize) -> Option<CellRole> {
         if x < self.width && y < self.height {
             Some(self.cells[y * self.width + x])
@@ -81,7 +96,18 @@ impl Anfield {
         }
     }
 
-    fn set_cell(&mut self, x: usize, y: usize, cell_role: CellRole) {
+    pub fn get_char(&self, cell_role: CellRole) -> char {
+        *self
+            .char_to_role
+            .get_by_value(&cell_role)
+            .expect("Invalid cell found in BiMap of Anfield")
+    }
+
+    pub fn get_role(&self, c: char) -> Option<CellRole> {
+        self.char_to_role.get_by_key(&c).copied()
+    }
+
+    pub(crate) fn set_cell(&mut self, x: usize, y: usize, cell_role: CellRole) {
         if x < self.width && y < self.height {
             self.cells[y * self.width + x] = cell_role;
         }
@@ -116,6 +142,49 @@ impl Anfield {
         Ok(())
     }
 
+    // Places a piece for `player` the way the engine does: the player's previous latest move becomes ordinary territory, and the new shape cells become the latest move. Legality isn't checked here (see `rules::check_placement`); shape cells that fall outside the Anfield are ignored.
+    pub fn apply(&mut self, piece: &Piece, x: i32, y: i32, player: Player) {
+        let symbol = player.symbol();
+        let latest_move = player.latest_move();
+        for cell in self.cells.iter_mut() {
+            if *cell == latest_move {
+                *cell = symbol;
+            }
+        }
+
+        for cell in &piece.shape {
+            let s = x as i64 + cell.x as i64;
+            let t = y as i64 + cell.y as i64;
+            if s >= 0 && t >= 0 {
+                self.set_cell(s as usize, t as usize, latest_move);
+            }
+        }
+    }
+
+    // Fills the piece's empty shape cells with `player`'s symbol and returns their indices, so that `clear` can undo the move. Unlike `apply`, it leaves the latest moves alone. It's for searches that try one move after another on the same scratch board instead of cloning it for each.
+    pub fn fill(&mut self, piece: &Piece, x: i32, y: i32, player: Player) -> Vec<usize> {
+        let mut filled = Vec::with_capacity(piece.shape.len());
+        for cell in &piece.shape {
+            let s = x as i64 + cell.x as i64;
+            let t = y as i64 + cell.y as i64;
+            if s >= 0 && t >= 0 && (s as usize) < self.width && (t as usize) < self.height {
+                let index = t as usize * self.width + s as usize;
+                if self.cells[index] == CellRole::Empty {
+                    self.cells[index] = player.symbol();
+                    filled.push(index);
+                }
+            }
+        }
+        filled
+    }
+
+    // Empties the cells that `fill` returned.
+    pub fn clear(&mut self, filled: &[usize]) {
+        for &index in filled {
+            self.cells[index] = CellRole::Empty;
+        }
+    }
+
     pub fn set_dimensions(&mut self, width: usize, height: usize) {
         assert!(self.width == 0, "Should not try to re-initialize Anfield");
         self.width = width;
@@ -123,3 +192,85 @@ impl Anfield {
         self.cells = vec![CellRole::Empty; width * height];
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    #[test]
+    fn test_apply_rolls_latest_move_forward() {
+        let mut anfield = Anfield::new(1);
+        anfield.set_dimensions(4, 2);
+        anfield.set_cell(0, 0, CellRole::OwnSymbol);
+        anfield.set_cell(3, 1, CellRole::OpponentSymbol);
+        let bar = Piece {
+            width: 2,
+            height: 1,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }],
+        };
+
+        anfield.apply(&bar, 0, 0, Player::Own);
+        assert_eq!(anfield.get_cell_role(0, 0), Some(CellRole::OwnLatestMove));
+        assert_eq!(anfield.get_cell_role(1, 0), Some(CellRole::OwnLatestMove));
+
+        anfield.apply(&bar, 2, 1, Player::Opponent);
+        assert_eq!(
+            anfield.get_cell_role(2, 1),
+            Some(CellRole::OpponentLatestMove)
+        );
+        assert_eq!(
+            anfield.get_cell_role(3, 1),
+            Some(CellRole::OpponentLatestMove)
+        );
+        assert_eq!(anfield.get_cell_role(0, 0), Some(CellRole::OwnLatestMove));
+
+        anfield.apply(&bar, 1, 0, Player::Own);
+        assert_eq!(anfield.get_cell_role(0, 0), Some(CellRole::OwnSymbol));
+        assert_eq!(anfield.get_cell_role(1, 0), Some(CellRole::OwnLatestMove));
+        assert_eq!(anfield.get_cell_role(2, 0), Some(CellRole::OwnLatestMove));
+        assert_eq!(
+            anfield.get_cell_role(3, 1),
+            Some(CellRole::OpponentLatestMove)
+        );
+    }
+
+    #[test]
+    fn test_apply_ignores_cells_outside_anfield() {
+        let mut anfield = Anfield::new(2);
+        anfield.set_dimensions(2, 2);
+        let square = Piece {
+            width: 2,
+            height: 2,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 1 }],
+        };
+
+        anfield.apply(&square, -1, -1, Player::Opponent);
+        let latest = CellRole::OpponentLatestMove;
+        assert_eq!(
+            anfield.cells,
+            vec![latest, CellRole::Empty, CellRole::Empty, CellRole::Empty]
+        );
+    }
+
+    #[test]
+    fn test_clear_undoes_fill() {
+        let mut anfield = Anfield::new(1);
+        anfield.set_dimensions(3, 1);
+        anfield.set_cell(0, 0, CellRole::OwnLatestMove);
+        let before = anfield.cells.clone();
+        let bar = Piece {
+            width: 2,
+            height: 1,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }],
+        };
+
+        let filled = anfield.fill(&bar, 0, 0, Player::Own);
+        assert_eq!(filled, vec![1]);
+        assert_eq!(anfield.get_cell_role(0, 0), Some(CellRole::OwnLatestMove));
+        assert_eq!(anfield.get_cell_role(1, 0), Some(CellRole::OwnSymbol));
+
+        anfield.clear(&filled);
+        assert_eq!(anfield.cells, before);
+    }
+}
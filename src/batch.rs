@@ -0,0 +1,80 @@
+// Plays many independent games at once. Each job carries everything that decides its game, seed included, so results don't depend on which thread ran what or when.
+
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// Runs `play` on every job using up to `threads` worker threads and returns the results in the order of the jobs. With `show_progress`, keeps a count of finished games on stderr.
+pub fn run<J, R, F>(jobs: &[J], threads: usize, show_progress: bool, play: F) -> Vec<R>
+where
+    J: Sync,
+    R: Send,
+    F: Fn(&J) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    let mut results: Vec<Option<R>> = (0..jobs.len()).map(|_| None).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            let tx = tx.clone();
+            let (next, play) = (&next, &play);
+            scope.spawn(move || {
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= jobs.len() || tx.send((i, play(&jobs[i]))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for (done, (i, result)) in rx.iter().enumerate() {
+            results[i] = Some(result);
+            if show_progress {
+                eprint!("\r{}/{} games", done + 1, jobs.len());
+                let _ = io::stderr().flush();
+            }
+        }
+        if show_progress && !jobs.is_empty() {
+            eprintln!();
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|r| r.expect("Every job should have a result"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_results_keep_job_order() {
+        let jobs: Vec<u64> = (0..40).collect();
+        let results = run(&jobs, 4, false, |&n| {
+            thread::sleep(Duration::from_millis((40 - n) % 7));
+            n * n
+        });
+        assert_eq!(results, jobs.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_no_jobs() {
+        let results: Vec<u8> = run(&[] as &[u8], 8, true, |&n| n);
+        assert!(results.is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BiMap<K: Eq + std::hash::Hash + Clone, V: Eq + std::hash::Hash + Clone> {
     forward: HashMap<K, V>,
     backward: HashMap<V, K>,
@@ -32,3 +32,9 @@ impl<K: Eq + std::hash::Hash + Clone, V: Eq + std::hash::Hash + Clone> BiMap<K,
         self.backward.get(v)
     }
 }
+
+impl<K: Eq + std::hash::Hash + Clone, V: Eq + std::hash::Hash + Clone> Default for BiMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
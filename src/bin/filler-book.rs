@@ -0,0 +1,84 @@
+use std::{path::PathBuf, process};
+
+use filler::{
+    book::{self, Book, Line},
+    transcript,
+};
+
+const USAGE: &str = "\
+Usage: filler-book TRANSCRIPT... [-n TURNS] [-o BOOK] [-a]
+Adds the first TURNS moves (default 6) of each won game to BOOK (default opening.book). With -a, every game counts, won or not.";
+
+struct Args {
+    transcripts: Vec<PathBuf>,
+    turns: usize,
+    out: PathBuf,
+    all: bool,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    let mut book = if args.out.exists() {
+        Book::load(&args.out)?
+    } else {
+        Book::default()
+    };
+
+    let mut won = 0;
+    let mut added = 0;
+    for path in &args.transcripts {
+        let transcript = transcript::load(path)?;
+        if !args.all && !book::won(&transcript) {
+            continue;
+        }
+        won += 1;
+        if let Some(line) = Line::from_transcript(&transcript, args.turns)
+            && book.add(line)
+        {
+            added += 1;
+        }
+    }
+
+    book.save(&args.out)?;
+    println!(
+        "{} of {} games used, {} new lines, {} in {}",
+        won,
+        args.transcripts.len(),
+        added,
+        book.lines.len(),
+        args.out.display()
+    );
+    Ok(())
+}
+
+fn get_args() -> Option<Args> {
+    let mut transcripts = Vec::new();
+    let mut turns = 6;
+    let mut out = PathBuf::from("opening.book");
+    let mut all = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--turns" => turns = args.next()?.parse().ok()?,
+            "-o" | "--out" => out = PathBuf::from(args.next()?),
+            "-a" | "--all" => all = true,
+            _ if !arg.starts_with('-') => transcripts.push(PathBuf::from(arg)),
+            _ => return None,
+        }
+    }
+
+    if transcripts.is_empty() || turns == 0 {
+        return None;
+    }
+    Some(Args {
+        transcripts,
+        turns,
+        out,
+        all,
+    })
+}
@@ -0,0 +1,89 @@
+use std::{io, path::PathBuf, process, time::Duration};
+
+use filler::{
+    engine::{self, Config, DEFAULT_TIMEOUT},
+    export::{Exporter, MatchRecord},
+    rng,
+};
+
+const USAGE: &str = "Usage: filler-engine -f MAP -p1 BOT -p2 BOT [-s SEED] [-t SECONDS] [-P PIECES] [-q] [--jsonl FILE] [--csv FILE]";
+
+struct Args {
+    config: Config,
+    jsonl: Option<PathBuf>,
+    csv: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let Args { config, jsonl, csv } = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    let stdout = io::stdout();
+    let report = engine::run(&config, &mut stdout.lock())?;
+    // The bots' stderr is captured rather than passed through, so show it once the game is over.
+    for (seat, stderr) in report.stderr.iter().enumerate() {
+        if !stderr.is_empty() {
+            eprintln!("p{} stderr:\n{}", seat + 1, stderr.trim_end());
+        }
+    }
+    for (seat, stuck) in report.stuck.iter().enumerate() {
+        if let Some(stuck) = stuck {
+            eprintln!("p{} stuck on {}", seat + 1, stuck);
+        }
+    }
+
+    let players = config.players.clone().map(|p| p.display().to_string());
+    let record = MatchRecord::new(
+        players,
+        config.map.display().to_string(),
+        config.seed,
+        &report,
+    );
+    Exporter::new(jsonl.as_deref(), csv.as_deref())?.write(&record)?;
+    Ok(())
+}
+
+fn get_args() -> Option<Args> {
+    let mut map = None;
+    let mut p1 = None;
+    let mut p2 = None;
+    let mut seed = rng::seed_from_time();
+    let mut timeout = DEFAULT_TIMEOUT;
+    let mut pieces = None;
+    let mut quiet = false;
+    let mut jsonl = None;
+    let mut csv = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" | "--file" => map = Some(PathBuf::from(args.next()?)),
+            "-p1" | "--player1" => p1 = Some(PathBuf::from(args.next()?)),
+            "-p2" | "--player2" => p2 = Some(PathBuf::from(args.next()?)),
+            "-s" | "--seed" => seed = args.next()?.parse().ok()?,
+            "-t" | "--time" => {
+                timeout = Duration::try_from_secs_f64(args.next()?.parse().ok()?).ok()?
+            }
+            "-P" | "--pieces" => pieces = Some(PathBuf::from(args.next()?)),
+            "-q" | "--quiet" => quiet = true,
+            "--jsonl" => jsonl = Some(PathBuf::from(args.next()?)),
+            "--csv" => csv = Some(PathBuf::from(args.next()?)),
+            _ => return None,
+        }
+    }
+
+    Some(Args {
+        config: Config {
+            map: map?,
+            players: [p1?, p2?],
+            seed,
+            pieces,
+            timeout,
+            quiet,
+        },
+        jsonl,
+        csv,
+    })
+}
@@ -0,0 +1,54 @@
+use std::process;
+
+use filler::{
+    map::{
+        self,
+        generator::{self, MapOptions, StartPositions},
+    },
+    rng,
+};
+
+const USAGE: &str =
+    "Usage: filler-map WIDTH HEIGHT [-s SEED] [-d MIN_DISTANCE] [--asymmetric] > MAP";
+
+fn main() {
+    let (options, seed) = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    print!("{}", map::render(&generator::generate(&options, 1, seed)));
+}
+
+fn get_args() -> Option<(MapOptions, u64)> {
+    let mut dimensions = Vec::new();
+    let mut seed = rng::seed_from_time();
+    let mut min_distance = None;
+    let mut mirrored = true;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-s" | "--seed" => seed = args.next()?.parse().ok()?,
+            "-d" | "--min-distance" => min_distance = Some(args.next()?.parse().ok()?),
+            "--asymmetric" => mirrored = false,
+            _ => dimensions.push(arg.parse::<usize>().ok()?),
+        }
+    }
+
+    let [width, height] = dimensions[..] else {
+        return None;
+    };
+    if width * height < 2 {
+        return None;
+    }
+
+    let mut options = MapOptions::new(width, height);
+    let min_distance = min_distance.unwrap_or((width + height) / 2);
+    options.starts = if mirrored {
+        StartPositions::Mirrored { min_distance }
+    } else {
+        StartPositions::Random { min_distance }
+    };
+    Some((options, seed))
+}
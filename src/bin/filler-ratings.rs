@@ -0,0 +1,38 @@
+use std::{path::PathBuf, process};
+
+use filler::ratings::Ledger;
+
+const USAGE: &str = "\
+Usage: filler-ratings FILE [show]
+       filler-ratings FILE record BOT_A BOT_B win|draw|loss";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let Some(path) = args.first().map(PathBuf::from) else {
+        usage();
+    };
+    let mut ledger = Ledger::load(&path)?;
+
+    match args[1..].iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        [] | ["show"] => {}
+        ["record", a, b, result] => {
+            let score = match result {
+                "win" | "1" => 1.0,
+                "draw" | "0.5" => 0.5,
+                "loss" | "0" => 0.0,
+                _ => usage(),
+            };
+            ledger.record(a, b, score);
+            ledger.save(&path)?;
+        }
+        _ => usage(),
+    }
+
+    print!("{}", ledger);
+    Ok(())
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
@@ -0,0 +1,60 @@
+use std::{path::PathBuf, process};
+
+use filler::{replay, strategy, transcript};
+
+const USAGE: &str = "Usage: filler-replay TRANSCRIPT [-S STRATEGY] [-a]";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let (path, name, show_all) = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        eprintln!("Strategies: {}", strategy::NAMES.join(", "));
+        process::exit(2);
+    });
+    let strategy = strategy::by_name(&name).unwrap_or_else(|| {
+        eprintln!("Unknown strategy {:?}", name);
+        process::exit(2);
+    });
+
+    let transcript = transcript::load(&path)?;
+    let reports = replay::replay(&transcript, &strategy);
+
+    println!(" turn   recorded  replayed");
+    for report in reports.iter().filter(|r| show_all || r.is_flagged()) {
+        println!("{}", report);
+    }
+
+    let changed = reports.iter().filter(|r| r.differs()).count();
+    let illegal = reports
+        .iter()
+        .filter(|r| r.illegal.is_some() && !r.stuck)
+        .count();
+    println!(
+        "{} of {} moves changed, {} illegal",
+        changed,
+        reports.len(),
+        illegal
+    );
+
+    if changed > 0 || illegal > 0 {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn get_args() -> Option<(PathBuf, String, bool)> {
+    let mut path = None;
+    let mut name = "attack".to_string();
+    let mut show_all = false;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-S" | "--strategy" => name = args.next()?,
+            "-a" | "--all" => show_all = true,
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(PathBuf::from(arg)),
+            _ => return None,
+        }
+    }
+
+    Some((path?, name, show_all))
+}
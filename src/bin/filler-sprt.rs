@@ -0,0 +1,123 @@
+use std::{path::PathBuf, process, time::Duration};
+
+use filler::{
+    contestant::{self, Contestant},
+    engine::DEFAULT_TIMEOUT,
+    export::{Exporter, MatchRecord},
+    rng,
+    scoring::GameResult,
+    sprt::{Decision, Sprt},
+};
+
+const USAGE: &str = "\
+Usage: filler-sprt A B -m MAP... [-e ELO] [--alpha A] [--beta B] [-n MAX_PAIRS] [-s SEED] [-t SECONDS] [--jsonl FILE] [--csv FILE]
+A and B are both strategy names or both bot executables.";
+
+struct Args {
+    contestants: [Contestant; 2],
+    maps: Vec<PathBuf>,
+    sprt: Sprt,
+    max_pairs: usize,
+    seed: u64,
+    timeout: Duration,
+    jsonl: Option<PathBuf>,
+    csv: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let Args {
+        contestants: [a, b],
+        maps,
+        mut sprt,
+        max_pairs,
+        seed,
+        timeout,
+        jsonl,
+        csv,
+    } = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    // A's score in one game, whichever seat A had.
+    let score = |result: &GameResult, a_seat: u8| match result.winner() {
+        Some(id) if id == a_seat => 1.0,
+        Some(_) => 0.0,
+        None => 0.5,
+    };
+
+    let mut exporter = Exporter::new(jsonl.as_deref(), csv.as_deref())?;
+
+    for i in 0..max_pairs {
+        let map = &maps[i % maps.len()];
+        let seed = seed.wrapping_add(i as u64);
+        let first = contestant::play_game([&a, &b], map, seed, timeout)?;
+        let second = contestant::play_game([&b, &a], map, seed, timeout)?;
+        sprt.record_pair(score(&first.result, 1) + score(&second.result, 2));
+        for (players, report) in [([&a, &b], &first), ([&b, &a], &second)] {
+            let names = players.map(|p| p.to_string());
+            let record = MatchRecord::new(names, map.display().to_string(), seed, report);
+            exporter.write(&record)?;
+        }
+
+        eprint!("\r{}", sprt);
+        if sprt.decision() != Decision::Continue {
+            break;
+        }
+    }
+    eprintln!();
+
+    println!("A: {}", a);
+    println!("B: {}", b);
+    println!("{}", sprt);
+    Ok(())
+}
+
+fn get_args() -> Option<Args> {
+    let mut contestants = Vec::new();
+    let mut maps = Vec::new();
+    let mut margin = 10.0;
+    let mut alpha = 0.05;
+    let mut beta = 0.05;
+    let mut max_pairs = 1000;
+    let mut seed = rng::seed_from_time();
+    let mut timeout = DEFAULT_TIMEOUT;
+    let mut jsonl = None;
+    let mut csv = None;
+
+    let mut reading_maps = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-m" | "--maps" => reading_maps = true,
+            "-e" | "--elo" => margin = args.next()?.parse().ok()?,
+            "--alpha" => alpha = args.next()?.parse().ok()?,
+            "--beta" => beta = args.next()?.parse().ok()?,
+            "-n" | "--pairs" => max_pairs = args.next()?.parse().ok()?,
+            "-s" | "--seed" => seed = args.next()?.parse().ok()?,
+            "-t" | "--time" => {
+                timeout = Duration::try_from_secs_f64(args.next()?.parse().ok()?).ok()?
+            }
+            "--jsonl" => jsonl = Some(PathBuf::from(args.next()?)),
+            "--csv" => csv = Some(PathBuf::from(args.next()?)),
+            _ if arg.starts_with('-') => return None,
+            _ if reading_maps => maps.push(PathBuf::from(arg)),
+            _ => contestants.push(Contestant::parse(&arg)),
+        }
+    }
+
+    let valid = |p: f64| p > 0.0 && p < 0.5;
+    if maps.is_empty() || margin <= 0.0 || !valid(alpha) || !valid(beta) {
+        return None;
+    }
+    Some(Args {
+        contestants: contestants.try_into().ok()?,
+        maps,
+        sprt: Sprt::new(margin, alpha, beta),
+        max_pairs,
+        seed,
+        timeout,
+        jsonl,
+        csv,
+    })
+}
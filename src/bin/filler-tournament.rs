@@ -0,0 +1,185 @@
+use std::{io, path::PathBuf, process, time::Duration};
+
+use filler::{
+    batch,
+    engine::{self, Config, DEFAULT_TIMEOUT, StuckReason},
+    errors::GameError,
+    export::{Exporter, MatchRecord},
+    ratings::Ledger,
+    rng,
+    tournament::{self, Standings},
+};
+
+const USAGE: &str = "Usage: filler-tournament -b BOT... -m MAP... [-n SEEDS] [-s SEED] [-t SECONDS] [-r RATINGS] [-j THREADS] [--jsonl FILE] [--csv FILE]";
+
+struct Args {
+    bots: Vec<PathBuf>,
+    maps: Vec<PathBuf>,
+    seeds: Vec<u64>,
+    timeout: Duration,
+    ratings: Option<PathBuf>,
+    threads: usize,
+    jsonl: Option<PathBuf>,
+    csv: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    let bot_names: Vec<String> = args.bots.iter().map(|b| b.display().to_string()).collect();
+    let mut per_map: Vec<Standings> = args
+        .maps
+        .iter()
+        .map(|m| Standings::new(&m.display().to_string(), &bot_names))
+        .collect();
+    let mut overall = Standings::new("overall", &bot_names);
+    let mut ledger = match &args.ratings {
+        Some(path) => Some(Ledger::load(path)?),
+        None => None,
+    };
+    let mut exporter = Exporter::new(args.jsonl.as_deref(), args.csv.as_deref())?;
+    // Whether any bot failed other than by running out of moves, or any game couldn't be played.
+    let mut failed = false;
+
+    let pairings = tournament::schedule(args.bots.len(), args.maps.len(), &args.seeds);
+    let reports = batch::run(&pairings, args.threads, true, |pairing| {
+        let config = Config {
+            map: args.maps[pairing.map].clone(),
+            players: pairing.players.map(|i| args.bots[i].clone()),
+            seed: pairing.seed,
+            pieces: None,
+            timeout: args.timeout,
+            quiet: true,
+        };
+        engine::run(&config, &mut io::sink())
+    });
+
+    for (pairing, report) in pairings.iter().zip(reports) {
+        let game = format!(
+            "{} vs {} on {} with seed {}",
+            bot_names[pairing.players[0]],
+            bot_names[pairing.players[1]],
+            per_map[pairing.map].title,
+            pairing.seed
+        );
+        // A game that couldn't be played is lost by the bot that couldn't be launched, or, if it was nobody's fault, left out. Either way the tournament goes on. It isn't exported, since there's no result to export.
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => {
+                failed = true;
+                eprintln!("{}: {}", game, e);
+                if let GameError::SpawnBot(id, _) = e {
+                    let seat = id as usize - 1;
+                    per_map[pairing.map].record_forfeit(pairing.players, seat);
+                    overall.record_forfeit(pairing.players, seat);
+                    if let Some(ledger) = &mut ledger {
+                        let [a, b] = pairing.players.map(|i| bot_names[i].as_str());
+                        ledger.record(a, b, if seat == 0 { 0.0 } else { 1.0 });
+                    }
+                }
+                continue;
+            }
+        };
+        // Getting stuck with no legal move left is how games end; an illegal move when there was a legal one is the bot's mistake, and a crash, a timeout or a malformed answer is a bot failure.
+        for (seat, stuck) in report.stuck.iter().enumerate() {
+            let Some(stuck) = stuck else {
+                continue;
+            };
+            let broke = matches!(
+                stuck.reason,
+                StuckReason::Exited(_) | StuckReason::Timeout(_) | StuckReason::Malformed(_)
+            );
+            if !stuck.could_move && !broke {
+                continue;
+            }
+            failed |= broke;
+            eprintln!(
+                "{} failed as p{} on {} with seed {} on {}",
+                bot_names[pairing.players[seat]],
+                seat + 1,
+                per_map[pairing.map].title,
+                pairing.seed,
+                stuck
+            );
+        }
+        let players = pairing.players.map(|i| bot_names[i].clone());
+        let map = per_map[pairing.map].title.clone();
+        exporter.write(&MatchRecord::new(players, map, pairing.seed, &report))?;
+        per_map[pairing.map].record(pairing.players, &report.result);
+        overall.record(pairing.players, &report.result);
+        if let Some(ledger) = &mut ledger {
+            let [a, b] = pairing.players.map(|i| bot_names[i].as_str());
+            let score = match report.result.winner() {
+                Some(1) => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            ledger.record(a, b, score);
+        }
+    }
+
+    for standings in &per_map {
+        println!("{}", standings);
+    }
+    print!("{}", overall);
+    if let (Some(ledger), Some(path)) = (&ledger, &args.ratings) {
+        ledger.save(path)?;
+        print!("\n{}", ledger);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn get_args() -> Option<Args> {
+    let mut bots = Vec::new();
+    let mut maps = Vec::new();
+    let mut count = 1;
+    let mut seed = rng::seed_from_time();
+    let mut timeout = DEFAULT_TIMEOUT;
+    let mut ratings = None;
+    let mut threads = batch::default_threads();
+    let mut jsonl = None;
+    let mut csv = None;
+
+    // Bots and maps each take every argument up to the next flag.
+    let mut list: Option<&mut Vec<PathBuf>> = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-b" | "--bots" => list = Some(&mut bots),
+            "-m" | "--maps" => list = Some(&mut maps),
+            "-n" | "--seeds" => count = args.next()?.parse().ok()?,
+            "-s" | "--seed" => seed = args.next()?.parse().ok()?,
+            "-t" | "--time" => {
+                timeout = Duration::try_from_secs_f64(args.next()?.parse().ok()?).ok()?
+            }
+            "-j" | "--threads" => threads = args.next()?.parse().ok()?,
+            "-r" | "--ratings" => ratings = Some(PathBuf::from(args.next()?)),
+            "--jsonl" => jsonl = Some(PathBuf::from(args.next()?)),
+            "--csv" => csv = Some(PathBuf::from(args.next()?)),
+            _ if !arg.starts_with('-') => list.as_mut()?.push(PathBuf::from(arg)),
+            _ => return None,
+        }
+    }
+
+    if bots.len() < 2 || maps.is_empty() || count == 0 {
+        return None;
+    }
+    let seeds = (0..count).map(|i| seed.wrapping_add(i)).collect();
+    Some(Args {
+        bots,
+        maps,
+        seeds,
+        timeout,
+        ratings,
+        threads,
+        jsonl,
+        csv,
+    })
+}
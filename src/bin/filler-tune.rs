@@ -0,0 +1,155 @@
+use std::{path::PathBuf, process};
+
+use filler::{
+    anfield::Anfield,
+    batch,
+    errors::GameError,
+    export::{Exporter, MatchRecord},
+    map::{
+        self,
+        generator::{self, MapOptions},
+    },
+    rng,
+    strategy::attack::params::Params,
+    tuning::{self, Generation, Options, Played},
+};
+
+const USAGE: &str = "\
+Usage: filler-tune [-m MAP...] [-p POPULATION] [-g GENERATIONS] [-n SEEDS] [-s SEED] [-j THREADS] [-o OUT] [--jsonl FILE] [--csv FILE]
+Without maps, plays on generated maps the size of map00 and map01.";
+
+// The sizes of the official map00 and map01. map02 is much slower to play on.
+const DEFAULT_SIZES: [(usize, usize); 2] = [(20, 15), (40, 30)];
+
+struct Args {
+    maps: Vec<PathBuf>,
+    options: Options,
+    out: PathBuf,
+    jsonl: Option<PathBuf>,
+    csv: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let Args {
+        maps,
+        options,
+        out,
+        jsonl,
+        csv,
+    } = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    let (boards, map_names): (Vec<Anfield>, Vec<String>) = if maps.is_empty() {
+        DEFAULT_SIZES
+            .iter()
+            .enumerate()
+            .map(|(i, &(w, h))| {
+                let seed = options.seed.wrapping_add(i as u64);
+                let board = generator::generate(&MapOptions::new(w, h), 1, seed);
+                (board, format!("generated {}x{} seed {}", w, h, seed))
+            })
+            .unzip()
+    } else {
+        maps.iter()
+            .map(|path| Ok((map::load(path, 1)?, path.display().to_string())))
+            .collect::<Result<Vec<_>, GameError>>()?
+            .into_iter()
+            .unzip()
+    };
+
+    // Every game is exported, with each side named by its parameters.
+    let mut exporter = Exporter::new(jsonl.as_deref(), csv.as_deref())?;
+    let mut exported = Ok(());
+    let name = |params: &Params| {
+        let lines: Vec<String> = params.to_string().lines().map(String::from).collect();
+        format!("attack: {}", lines.join(", "))
+    };
+
+    // Save whenever a generation finds better parameters, so that stopping early still leaves the best so far.
+    let mut saved = Ok(());
+    let report = |generation: &Generation| {
+        eprintln!();
+        println!(
+            "generation {}: best {:.3} against the defaults with {:?}{}",
+            generation.number + 1,
+            generation.fitness,
+            generation.best,
+            if generation.improved { " (new)" } else { "" }
+        );
+        if generation.improved && saved.is_ok() {
+            saved = generation.best.save(&out);
+        }
+    };
+    let played = |game: &Played| {
+        if exported.is_ok() {
+            let players = game.players.map(|params| name(&params));
+            let map = map_names[game.map].clone();
+            let record = MatchRecord {
+                players,
+                map,
+                seed: game.seed,
+                result: game.result.clone(),
+                stuck_reasons: [None, None],
+            };
+            exported = exporter.write(&record);
+        }
+    };
+    let best = tuning::tune(&boards, &options, report, played);
+    saved?;
+    exported?;
+
+    print!("\n{}", best.best);
+    if best.best == Params::default() {
+        println!("No improvement on the defaults, so nothing was saved");
+    } else {
+        println!("Saved to {}", out.display());
+    }
+    Ok(())
+}
+
+fn get_args() -> Option<Args> {
+    let mut maps = Vec::new();
+    let mut options = Options {
+        population: 16,
+        generations: 10,
+        seeds: 4,
+        seed: rng::seed_from_time(),
+        threads: batch::default_threads(),
+        show_progress: true,
+    };
+    let mut out = PathBuf::from("attack.params");
+    let mut jsonl = None;
+    let mut csv = None;
+
+    let mut reading_maps = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-m" | "--maps" => reading_maps = true,
+            "-p" | "--population" => options.population = args.next()?.parse().ok()?,
+            "-g" | "--generations" => options.generations = args.next()?.parse().ok()?,
+            "-n" | "--seeds" => options.seeds = args.next()?.parse().ok()?,
+            "-s" | "--seed" => options.seed = args.next()?.parse().ok()?,
+            "-j" | "--threads" => options.threads = args.next()?.parse().ok()?,
+            "-o" | "--out" => out = PathBuf::from(args.next()?),
+            "--jsonl" => jsonl = Some(PathBuf::from(args.next()?)),
+            "--csv" => csv = Some(PathBuf::from(args.next()?)),
+            _ if arg.starts_with('-') => return None,
+            _ if reading_maps => maps.push(PathBuf::from(arg)),
+            _ => return None,
+        }
+    }
+
+    if options.population < 2 || options.generations == 0 || options.seeds == 0 {
+        return None;
+    }
+    Some(Args {
+        maps,
+        options,
+        out,
+        jsonl,
+        csv,
+    })
+}
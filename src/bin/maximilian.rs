@@ -1,7 +1,232 @@
-use filler::run;
-use filler::strategy::attack::Attack;
+use std::{path::PathBuf, process, time::Duration};
 
+use filler::{
+    book::{Book, Opening},
+    errors::GameError,
+    run,
+    strategy::{
+        self, Strategy,
+        attack::{
+            Attack,
+            params::{self, Params},
+        },
+        expectimax::Expectimax,
+        mcts::Mcts,
+        packing::PackWhenSeparated,
+        phased::{self, PHASES, Phase, Phased},
+    },
+};
+
+const USAGE: &str = "\
+Usage: maximilian [-T TRANSCRIPT] [-S STRATEGY] [-b BOOK] [-p PARAMS] [--distance-weight X] [--border-width N] [--border-cost X] [--centre-bonus X] [--depth N] [--samples N] [--breadth N] [--budget MS] [--opening S] [--contact S] [--separated S] [--opponent-stuck S]
+The strategy can also be set with FILLER_STRATEGY, the book with FILLER_BOOK, and each parameter with an environment variable, e.g. FILLER_BORDER_WIDTH, FILLER_DEPTH or FILLER_CONTACT.";
+
+// Picks the strategy when no `--strategy` is given.
+const STRATEGY_VAR: &str = "FILLER_STRATEGY";
+// An opening book to play from before the strategy takes over, when no `--book` is given.
+const BOOK_VAR: &str = "FILLER_BOOK";
+
+struct Args {
+    transcript: Option<PathBuf>,
+    strategy: Option<String>,
+    book: Option<PathBuf>,
+    params: Option<PathBuf>,
+    // `key = value` lines, applied in order.
+    overrides: Vec<String>,
+    search: SearchSettings,
+    // The phased strategy's strategy for each phase, in the order of `phased::PHASES`.
+    phases: [Option<String>; 4],
+}
+
+// Expectimax's depth, samples, breadth and budget, and MCTS's samples and budget. Budgets are in milliseconds. Those not given keep the strategy's defaults.
+#[derive(Debug, Clone, Copy, Default)]
+struct SearchSettings {
+    depth: Option<usize>,
+    samples: Option<usize>,
+    breadth: Option<usize>,
+    budget: Option<usize>,
+}
+
+impl SearchSettings {
+    fn get_mut(&mut self, key: &str) -> Option<&mut Option<usize>> {
+        match key {
+            "depth" => Some(&mut self.depth),
+            "samples" => Some(&mut self.samples),
+            "breadth" => Some(&mut self.breadth),
+            "budget" => Some(&mut self.budget),
+            _ => None,
+        }
+    }
+}
+
+const SEARCH_KEYS: [&str; 4] = ["depth", "samples", "breadth", "budget"];
+
+// The environment variable for a search setting, e.g. `FILLER_DEPTH`.
+fn search_var(key: &str) -> String {
+    format!("FILLER_{}", key.to_uppercase())
+}
+
+// The environment variable for the phased strategy's strategy in a phase, e.g. `FILLER_CONTACT`.
+fn phase_var(phase: Phase) -> String {
+    format!("FILLER_{}", phase.name().to_uppercase())
+}
+
+// The Attack parameters come from the defaults, then the `--params` file, then environment variables, then flags, each overriding the last.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    run::run(Attack)?;
+    let args = get_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    let mut params = match &args.params {
+        Some(path) => Params::load(path)?,
+        None => Params::default(),
+    };
+    params.apply_env()?;
+    for line in &args.overrides {
+        params
+            .set(line)
+            .ok_or_else(|| GameError::ParseParams(line.clone()))?;
+    }
+
+    // Without a strategy named, Attack plays until the players are separated and packing takes over from there.
+    let name = args
+        .strategy
+        .clone()
+        .or_else(|| std::env::var(STRATEGY_VAR).ok());
+    let strategy = match name {
+        Some(name) => build(&name, params, &args)?,
+        None => Box::new(PackWhenSeparated(Attack::new(params))),
+    };
+    let book = args
+        .book
+        .clone()
+        .or_else(|| std::env::var_os(BOOK_VAR).map(PathBuf::from));
+    let strategy: Box<dyn Strategy> = match book {
+        Some(path) => Box::new(Opening::new(Book::load(&path)?, strategy)),
+        None => strategy,
+    };
+    match args.transcript {
+        Some(path) => run::run_with_transcript(strategy, Some(path))?,
+        None => run::run(strategy)?,
+    }
     Ok(())
 }
+
+// The strategy called `name`, set up from the command line and environment. The phased strategy's phases are built the same way, by `build_single`, so that e.g. its Attack uses the `--params`.
+fn build(name: &str, params: Params, args: &Args) -> Result<Box<dyn Strategy>, GameError> {
+    Ok(match name {
+        "phased" => {
+            let mut phased = Phased::default();
+            for ((phase, flag), default) in
+                PHASES.into_iter().zip(&args.phases).zip(phased::DEFAULTS)
+            {
+                let name = flag
+                    .clone()
+                    .or_else(|| std::env::var(phase_var(phase)).ok())
+                    .unwrap_or_else(|| default.to_string());
+                phased.set(phase, build_single(&name, params, args)?)?;
+            }
+            Box::new(phased)
+        }
+        _ => build_single(name, params, args)?,
+    })
+}
+
+// Any strategy but the phased one, which is built from these. A phased strategy named for one of its phases comes with its default phases, and `Phased::set` turns it down.
+fn build_single(name: &str, params: Params, args: &Args) -> Result<Box<dyn Strategy>, GameError> {
+    Ok(match name {
+        "attack" => Box::new(Attack::new(params)),
+        "expectimax" => {
+            let mut search = Expectimax::default();
+            let settings = search_settings(args.search)?;
+            search.depth = settings.depth.unwrap_or(search.depth);
+            search.samples = settings.samples.unwrap_or(search.samples);
+            search.breadth = settings.breadth.unwrap_or(search.breadth);
+            search.budget = settings
+                .budget
+                .map_or(search.budget, |ms| Duration::from_millis(ms as u64));
+            Box::new(search)
+        }
+        "mcts" => {
+            let mut search = Mcts::default();
+            let settings = search_settings(args.search)?;
+            search.samples = settings.samples.unwrap_or(search.samples);
+            search.budget = settings
+                .budget
+                .map_or(search.budget, |ms| Duration::from_millis(ms as u64));
+            Box::new(search)
+        }
+        _ => strategy::by_name(name).unwrap_or_else(|| unknown(name)),
+    })
+}
+
+fn unknown(name: &str) -> ! {
+    eprintln!(
+        "Unknown strategy {:?}; try one of {:?}",
+        name,
+        strategy::NAMES
+    );
+    process::exit(2);
+}
+
+// Flags, or else environment variables.
+fn search_settings(flags: SearchSettings) -> Result<SearchSettings, GameError> {
+    let mut settings = flags;
+    for key in SEARCH_KEYS {
+        let setting = settings.get_mut(key).expect("A search key");
+        if setting.is_none()
+            && let Ok(value) = std::env::var(search_var(key))
+        {
+            let parsed = value.parse().ok();
+            *setting = Some(parsed.ok_or(GameError::ParseParams(format!("{} = {}", key, value)))?);
+        }
+    }
+    Ok(settings)
+}
+
+// `--transcript PATH` overrides the `FILLER_TRANSCRIPT` environment variable. `--params PATH` loads the Attack parameters written by `filler-tune`.
+fn get_args() -> Option<Args> {
+    let mut transcript = None;
+    let mut strategy = None;
+    let mut book = None;
+    let mut params = None;
+    let mut overrides = Vec::new();
+    let mut search = SearchSettings::default();
+    let mut phases = [const { None }; 4];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-T" | "--transcript" => transcript = Some(PathBuf::from(args.next()?)),
+            "-S" | "--strategy" => strategy = Some(args.next()?),
+            "-b" | "--book" => book = Some(PathBuf::from(args.next()?)),
+            "-p" | "--params" => params = Some(PathBuf::from(args.next()?)),
+            _ => {
+                let key = arg.strip_prefix("--")?.replace('-', "_");
+                if let Some(setting) = search.get_mut(&key) {
+                    *setting = Some(args.next()?.parse().ok()?);
+                    continue;
+                }
+                if let Some(i) = PHASES.iter().position(|phase| phase.name() == key) {
+                    phases[i] = Some(args.next()?);
+                    continue;
+                }
+                if !params::KEYS.contains(&key.as_str()) {
+                    return None;
+                }
+                overrides.push(format!("{} = {}", key, args.next()?));
+            }
+        }
+    }
+
+    Some(Args {
+        transcript,
+        strategy,
+        book,
+        params,
+        overrides,
+        search,
+        phases,
+    })
+}
@@ -76,7 +76,7 @@ fn spawn_input_thread(tx: mpsc::Sender<(usize, usize, Vec<Vec<char>>)>) {
         let stdin = io::stdin();
         let mut parser = GridParser::new();
 
-        for line in stdin.lock().lines().filter_map(Result::ok) {
+        for line in stdin.lock().lines().map_while(Result::ok) {
             println!("{}", line);
             if let Some(grid_data) = parser.process_line(&line) {
                 let _ = tx.send(grid_data);
@@ -16,10 +16,11 @@ impl GridParser {
     pub fn process_line(&mut self, line: &str) -> Option<(usize, usize, Vec<Vec<char>>)> {
         if line.starts_with("Anfield ") {
             self.parse_header(line);
-        } else if self.width > 0 && self.height > 0 {
-            if let Some(grid_data) = self.try_parse_grid_row(line) {
-                return Some(grid_data);
-            }
+        } else if self.width > 0
+            && self.height > 0
+            && let Some(grid_data) = self.try_parse_grid_row(line)
+        {
+            return Some(grid_data);
         }
         None
     }
@@ -57,9 +58,9 @@ impl GridParser {
 
     fn is_grid_data_line(&self, line: &str) -> bool {
         line.len() >= 4
-            && line.chars().nth(0).map_or(false, |c| c.is_ascii_digit())
-            && line.chars().nth(1).map_or(false, |c| c.is_ascii_digit())
-            && line.chars().nth(2).map_or(false, |c| c.is_ascii_digit())
+            && line.chars().nth(0).is_some_and(|c| c.is_ascii_digit())
+            && line.chars().nth(1).is_some_and(|c| c.is_ascii_digit())
+            && line.chars().nth(2).is_some_and(|c| c.is_ascii_digit())
             && line.chars().nth(3) == Some(' ')
     }
 }
@@ -44,9 +44,9 @@ pub fn render_grid_to_buffer(
 ) {
     let buf_width = width * scale;
 
-    for y in 0..height {
-        for x in 0..width {
-            let color = match grid[y][x] {
+    for (y, row) in grid.iter().enumerate().take(height) {
+        for (x, &c) in row.iter().enumerate().take(width) {
+            let color = match c {
                 '@' => 0xFF0000, // red
                 '$' => 0x00FF00, // green
                 'a' => 0xFFAAAA, // light red
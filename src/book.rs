@@ -0,0 +1,472 @@
+// An opening book: sequences of moves that won from the start of a game, stored by board size, seat and start cells, to be replayed while the game follows one of them. A game is still "in book" when our cells are exactly the ones a stored line would have placed so far; then that line's next move is played if it's legal with the piece in hand. Once none apply, the main strategy takes over for good, since our cells can't come back into line.
+//
+// A book is a text file with one line per opening: the board's width and height, our seat, our start cells and then the opponent's, as `x,y` joined by `;`, and then after each `|` a move and the piece it placed, rows joined by `/`:
+//
+// 40 30 p1 4,3 32,26 | 4 2 .O/OO | 5 4 OOO
+
+use std::{collections::BTreeSet, fmt, fs, path::Path};
+
+use crate::{
+    anfield::Anfield,
+    cell::Cell,
+    errors::GameError,
+    piece::Piece,
+    rules,
+    strategy::Strategy,
+    symbols::{self, CellRole, Player},
+    territory,
+    transcript::Transcript,
+};
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    pub width: usize,
+    pub height: usize,
+    pub seat: u8,
+    // Our start cells and the opponent's, as indexes into `Anfield::cells`.
+    pub starts: [Vec<usize>; 2],
+    pub moves: Vec<([i32; 2], Piece)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Book {
+    pub lines: Vec<Line>,
+}
+
+impl Line {
+    // The first `turns` moves of a recorded game, up to the first illegal one.
+    pub fn from_transcript(transcript: &Transcript, turns: usize) -> Option<Line> {
+        let first = &transcript.turns.first()?.anfield;
+        // The opponent may have moved once before our first turn, but only its latest move is marked as such, so the rest are its start cells.
+        let cells_of = |role: CellRole| {
+            (0..first.cells.len())
+                .filter(|&i| first.cells[i] == role)
+                .collect()
+        };
+        let starts = [
+            (0..first.cells.len())
+                .filter(|&i| territory::belongs_to(first, i, Player::Own))
+                .collect(),
+            cells_of(CellRole::OpponentSymbol),
+        ];
+
+        let moves = transcript
+            .turns
+            .iter()
+            .take(turns)
+            .take_while(|turn| {
+                let [x, y] = turn.chosen;
+                rules::check_placement(&turn.anfield, &turn.piece, x, y).is_ok()
+            })
+            .map(|turn| (turn.chosen, turn.piece.clone()))
+            .collect::<Vec<_>>();
+        if moves.is_empty() {
+            return None;
+        }
+
+        Some(Line {
+            width: first.width,
+            height: first.height,
+            seat: transcript.own_id,
+            starts,
+            moves,
+        })
+    }
+
+    fn fits(&self, anfield: &Anfield) -> bool {
+        let seat = if anfield.get_char(CellRole::OwnSymbol) == symbols::P1_CHAR {
+            1
+        } else {
+            2
+        };
+        self.width == anfield.width
+            && self.height == anfield.height
+            && self.seat == seat
+            && self.starts[0]
+                .iter()
+                .all(|&i| territory::belongs_to(anfield, i, Player::Own))
+            && self.starts[1]
+                .iter()
+                .all(|&i| territory::belongs_to(anfield, i, Player::Opponent))
+    }
+
+    fn same_as(&self, other: &Line) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.seat == other.seat
+            && self.starts == other.starts
+            && self.moves.len() == other.moves.len()
+            && self
+                .moves
+                .iter()
+                .zip(&other.moves)
+                .all(|((a, p), (b, q))| a == b && same_piece(p, q))
+    }
+
+    // The stored move for the position where our cells are `own`, if the game has followed this line so far.
+    fn next_move(&self, own: &BTreeSet<usize>) -> Option<&([i32; 2], Piece)> {
+        let mut expected: BTreeSet<usize> = self.starts[0].iter().copied().collect();
+        for entry in &self.moves {
+            if expected.len() >= own.len() {
+                return (expected == *own).then_some(entry);
+            }
+            let ([x, y], piece) = entry;
+            expected.extend(piece.shape.iter().map(|cell| {
+                (y + cell.y as i32) as usize * self.width + (x + cell.x as i32) as usize
+            }));
+        }
+        None
+    }
+}
+
+impl Book {
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    // Blank lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self, GameError> {
+        let lines = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| parse_line(line).ok_or_else(|| GameError::ParseBook(line.to_string())))
+            .collect::<Result<_, _>>()?;
+        Ok(Book { lines })
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    // Adds a line unless the book already has it. Returns whether it was new.
+    pub fn add(&mut self, line: Line) -> bool {
+        if self.lines.iter().any(|known| known.same_as(&line)) {
+            return false;
+        }
+        self.lines.push(line);
+        true
+    }
+
+    // The book move for this position, if there is one and it's legal. A line that stored the same piece comes first, since that's the game it was recorded in.
+    pub fn choose(&self, anfield: &Anfield, piece: &Piece) -> Option<[i32; 2]> {
+        let own: BTreeSet<usize> = (0..anfield.cells.len())
+            .filter(|&i| territory::belongs_to(anfield, i, Player::Own))
+            .collect();
+        let candidates: Vec<&([i32; 2], Piece)> = self
+            .lines
+            .iter()
+            .filter(|line| line.fits(anfield))
+            .filter_map(|line| line.next_move(&own))
+            .filter(|([x, y], _)| rules::check_placement(anfield, piece, *x, *y).is_ok())
+            .collect();
+        candidates
+            .iter()
+            .find(|(_, stored)| same_piece(stored, piece))
+            .or(candidates.first())
+            .map(|(mv, _)| *mv)
+    }
+}
+
+// Plays from the book while it can, and then as `fallback` would. It's for one game: once play has left the book, the book isn't looked at again.
+pub struct Opening<S: Strategy> {
+    pub book: Book,
+    pub fallback: S,
+    left_book: std::cell::Cell<bool>,
+}
+
+impl<S: Strategy> Opening<S> {
+    pub fn new(book: Book, fallback: S) -> Self {
+        Opening {
+            book,
+            fallback,
+            left_book: std::cell::Cell::new(false),
+        }
+    }
+}
+
+impl<S: Strategy> Strategy for Opening<S> {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        if !self.left_book.get() {
+            if let Some(mv) = self.book.choose(anfield, piece) {
+                return mv;
+            }
+            self.left_book.set(true);
+        }
+        self.fallback.choose_move(anfield, piece)
+    }
+}
+
+// Whether the bot that recorded a transcript can be sure it won. Each bot's last turn is the one it got stuck on, so the board then shows its final count, but the opponent's only if the opponent had already stopped placing pieces by then.
+pub fn won(transcript: &Transcript) -> bool {
+    let count = |anfield: &Anfield, player| {
+        (0..anfield.cells.len())
+            .filter(|&i| territory::belongs_to(anfield, i, player))
+            .count()
+    };
+    let [.., before, last] = transcript.turns.as_slice() else {
+        return false;
+    };
+    let opponent = count(&last.anfield, Player::Opponent);
+    opponent == count(&before.anfield, Player::Opponent)
+        && count(&last.anfield, Player::Own) > opponent
+}
+
+fn same_piece(a: &Piece, b: &Piece) -> bool {
+    a.width == b.width && a.height == b.height && a.shape == b.shape
+}
+
+fn parse_line(text: &str) -> Option<Line> {
+    let mut parts = text.split('|').map(str::trim);
+    let header: Vec<&str> = parts.next()?.split_whitespace().collect();
+    let [width, height, seat, own, opponent] = header.as_slice() else {
+        return None;
+    };
+    let width: usize = width.parse().ok()?;
+    let height: usize = height.parse().ok()?;
+    let seat = match *seat {
+        "p1" => 1,
+        "p2" => 2,
+        _ => return None,
+    };
+    let cells = |text: &str| -> Option<Vec<usize>> {
+        text.split(';')
+            .map(|xy| {
+                let (x, y) = xy.split_once(',')?;
+                let (x, y): (usize, usize) = (x.parse().ok()?, y.parse().ok()?);
+                (x < width && y < height).then_some(y * width + x)
+            })
+            .collect()
+    };
+    let starts = [cells(own)?, cells(opponent)?];
+
+    // Every cell of every move must land on the board, as a legal move's would.
+    let moves = parts
+        .map(|part| {
+            let [x, y, rows] = part.split_whitespace().collect::<Vec<_>>()[..] else {
+                return None;
+            };
+            let [x, y]: [i32; 2] = [x.parse().ok()?, y.parse().ok()?];
+            let piece = parse_piece(rows)?;
+            let on_board = piece.shape.iter().all(|cell| {
+                let (s, t) = (x as i64 + cell.x as i64, y as i64 + cell.y as i64);
+                (0..width as i64).contains(&s) && (0..height as i64).contains(&t)
+            });
+            on_board.then_some(([x, y], piece))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Line {
+        width,
+        height,
+        seat,
+        starts,
+        moves,
+    })
+}
+
+fn parse_piece(text: &str) -> Option<Piece> {
+    let rows: Vec<&str> = text.split('/').collect();
+    let mut shape = Vec::new();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            match c {
+                symbols::NEW_PIECE_CHAR => shape.push(Cell { x, y }),
+                symbols::EMPTY_CHAR => (),
+                _ => return None,
+            }
+        }
+    }
+    Some(Piece {
+        width: rows.iter().map(|row| row.len()).max()?,
+        height: rows.len(),
+        shape,
+    })
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells = |indexes: &[usize]| {
+            indexes
+                .iter()
+                .map(|i| format!("{},{}", i % self.width, i / self.width))
+                .collect::<Vec<_>>()
+                .join(";")
+        };
+        write!(
+            f,
+            "{} {} p{} {} {}",
+            self.width,
+            self.height,
+            self.seat,
+            cells(&self.starts[0]),
+            cells(&self.starts[1])
+        )?;
+        for ([x, y], piece) in &self.moves {
+            let rows: Vec<String> = (0..piece.height)
+                .map(|y| {
+                    (0..piece.width)
+                        .map(|x| {
+                            if piece.shape.contains(&Cell { x, y }) {
+                                symbols::NEW_PIECE_CHAR
+                            } else {
+                                symbols::EMPTY_CHAR
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+            write!(f, " | {} {} {}", x, y, rows.join("/"))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Book {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "# width height seat own-starts opponent-starts | x y piece | ..."
+        )?;
+        for line in &self.lines {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        map,
+        piece::generator::PieceGenerator,
+        strategy::{attack::Attack, voronoi::Voronoi},
+        transcript::Turn,
+    };
+    use std::time::Duration;
+
+    // Gives up every time, so that it's plain when the fallback was asked.
+    struct Resign;
+
+    impl Strategy for Resign {
+        fn choose_move(&self, _anfield: &Anfield, _piece: &Piece) -> [i32; 2] {
+            [-1, -1]
+        }
+    }
+
+    // Our side of a game between two strategies, as a transcript would record it.
+    fn record(turns: usize) -> Transcript {
+        let mut anfield =
+            map::parse("@.........\n..........\n..........\n.........$", 1).expect("Valid map");
+        let mut pieces = PieceGenerator::new(4, 3);
+        let mut recorded = Vec::new();
+        for _ in 0..turns {
+            let piece = pieces.next_piece();
+            let chosen = Voronoi.choose_move(&anfield, &piece);
+            recorded.push(Turn {
+                anfield: anfield.clone(),
+                piece: piece.clone(),
+                chosen,
+                elapsed: Duration::ZERO,
+            });
+            anfield.apply(&piece, chosen[0], chosen[1], Player::Own);
+            let reply = pieces.next_piece();
+            let [x, y] = Attack::default().choose_move(&anfield.swapped(), &reply);
+            if rules::check_placement(&anfield.swapped(), &reply, x, y).is_ok() {
+                anfield.apply(&reply, x, y, Player::Opponent);
+            }
+        }
+        Transcript {
+            own_id: 1,
+            turns: recorded,
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let line = Line::from_transcript(&record(3), 3).expect("A line");
+        let text = line.to_string();
+        assert!(text.starts_with("10 4 p1 0,0 9,3 | "), "{}", text);
+
+        let book = Book::parse(&format!("# a book\n\n{}\n", text)).expect("Valid book");
+        assert_eq!(book.lines.len(), 1);
+        assert_eq!(book.lines[0].to_string(), text);
+        for bad in [
+            "10 4 p3 0,0 9,3 | 0 0 O",
+            "10 4 p1 0,0 9,3 | 9 0 OO",
+            "10 4 p1 0,0 9,3 | 0 -1 O",
+        ] {
+            assert!(
+                matches!(Book::parse(bad), Err(GameError::ParseBook(_))),
+                "{}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_replays_the_line_then_falls_back() {
+        let transcript = record(4);
+        let mut book = Book::default();
+        assert!(book.add(Line::from_transcript(&transcript, 2).expect("A line")));
+        assert!(!book.add(Line::from_transcript(&transcript, 2).expect("A line")));
+
+        let opening = Opening::new(book, Attack::default());
+        for turn in &transcript.turns[..2] {
+            assert_eq!(
+                opening.book.choose(&turn.anfield, &turn.piece),
+                Some(turn.chosen)
+            );
+        }
+        // Past the end of the line, and on a board the line doesn't fit, it's the fallback's move.
+        let turn = &transcript.turns[2];
+        assert_eq!(opening.book.choose(&turn.anfield, &turn.piece), None);
+        assert_eq!(
+            opening.choose_move(&turn.anfield, &turn.piece),
+            Attack::default().choose_move(&turn.anfield, &turn.piece)
+        );
+        let swapped = transcript.turns[0].anfield.swapped();
+        assert_eq!(
+            opening.book.choose(&swapped, &transcript.turns[0].piece),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stays_out_of_the_book_once_it_leaves() {
+        let transcript = record(3);
+        let mut book = Book::default();
+        book.add(Line::from_transcript(&transcript, 2).expect("A line"));
+        let opening = Opening::new(book, Resign);
+
+        let [first, _, third] = &transcript.turns[..] else {
+            unreachable!()
+        };
+        assert_eq!(
+            opening.choose_move(&first.anfield, &first.piece),
+            first.chosen
+        );
+        assert_eq!(opening.choose_move(&third.anfield, &third.piece), [-1, -1]);
+        // The first position is still in the book, but play has left it.
+        assert_eq!(opening.choose_move(&first.anfield, &first.piece), [-1, -1]);
+    }
+
+    #[test]
+    fn test_won_needs_the_opponent_to_have_stopped() {
+        let turn = |board: &str| Turn {
+            anfield: map::parse(board, 1).expect("Valid map"),
+            piece: PieceGenerator::new(1, 2).next_piece(),
+            chosen: [0, 0],
+            elapsed: Duration::ZERO,
+        };
+        let transcript = |boards: &[&str]| Transcript {
+            own_id: 1,
+            turns: boards.iter().map(|board| turn(board)).collect(),
+        };
+        assert!(won(&transcript(&["@@...$", "@@@..$"])));
+        assert!(!won(&transcript(&["@@...$", "@@@.$$"])));
+        assert!(!won(&transcript(&["@..$$$", "@..$$$"])));
+        assert!(!won(&transcript(&["@@@..$"])));
+    }
+}
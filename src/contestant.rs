@@ -0,0 +1,72 @@
+// Something that can play a game: either one of the library's strategies, played in-process, or a bot executable, played through the engine.
+
+use std::{fmt, io, path::Path, path::PathBuf, time::Duration};
+
+use crate::{
+    engine::{self, Config, MatchReport},
+    errors::GameError,
+    map,
+    simulate::simulate,
+    strategy,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Contestant {
+    Strategy(String),
+    Binary(PathBuf),
+}
+
+impl Contestant {
+    // A strategy if the name is one, otherwise a path to an executable.
+    pub fn parse(arg: &str) -> Self {
+        if strategy::by_name(arg).is_some() {
+            Contestant::Strategy(arg.to_string())
+        } else {
+            Contestant::Binary(PathBuf::from(arg))
+        }
+    }
+}
+
+impl fmt::Display for Contestant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Contestant::Strategy(name) => write!(f, "{}", name),
+            Contestant::Binary(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+// Two strategies play in-process and two binaries through `filler-engine`'s engine; the same map and seed deal the same pieces either way. Only the engine knows why a player got stuck. A strategy can't play a binary.
+pub fn play_game(
+    players: [&Contestant; 2],
+    map_path: &Path,
+    seed: u64,
+    timeout: Duration,
+) -> Result<MatchReport, GameError> {
+    match players {
+        [Contestant::Strategy(a), Contestant::Strategy(b)] => {
+            let anfield = map::load(map_path, 1)?;
+            let [a, b] = [a, b].map(|name| strategy::by_name(name).expect("Known strategy"));
+            Ok(MatchReport {
+                result: simulate(&a, &b, &anfield, seed),
+                stuck: [None, None],
+                stderr: Default::default(),
+            })
+        }
+        [Contestant::Binary(a), Contestant::Binary(b)] => {
+            let config = Config {
+                map: map_path.to_path_buf(),
+                players: [a.clone(), b.clone()],
+                seed,
+                pieces: None,
+                timeout,
+                quiet: true,
+            };
+            engine::run(&config, &mut io::sink())
+        }
+        _ => Err(GameError::MixedContestants(format!(
+            "{} and {}",
+            players[0], players[1]
+        ))),
+    }
+}
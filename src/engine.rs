@@ -0,0 +1,356 @@
+// A stand-in for the closed-source `linux_game_engine`: it loads a map, launches two bots, sends them the board and a random piece in turn, and applies their answers until neither can move.
+
+use std::{
+    fmt,
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    sync::{
+        Arc, Mutex,
+        mpsc::{self, Receiver, RecvTimeoutError},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    anfield::Anfield,
+    errors::GameError,
+    map,
+    piece::{Piece, generator::PieceGenerator},
+    protocol,
+    rules::{self, IllegalMove},
+    scoring::{GameResult, SEAT_NAMES, Scoreboard},
+    symbols::Player,
+};
+
+pub struct Config {
+    pub map: PathBuf,
+    pub players: [PathBuf; 2],
+    pub seed: u64,
+    // Replay these pieces instead of dealing random ones, so that a rematch with seats swapped can be played with exactly the same pieces.
+    pub pieces: Option<PathBuf>,
+    // How long each bot may think about each move, like the official engine's `-t`.
+    pub timeout: Duration,
+    pub quiet: bool,
+}
+
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// How much of the end of each bot's stderr to keep.
+const STDERR_LIMIT: usize = 16 * 1024;
+// How long to wait for a bot that has closed its stdout to exit, and for the last of its stderr, before giving up on them.
+const EXIT_GRACE: Duration = Duration::from_millis(500);
+
+// Why a bot stopped playing. The official engine treats all of these alike, but knowing which one it was matters when a game is lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StuckReason {
+    Illegal(IllegalMove),
+    Malformed(String),
+    Timeout(Duration),
+    Exited(Exit),
+}
+
+// What is known about a bot that stopped answering: its exit status, if it did exit, and the message it panicked with, if it was a Rust program that panicked.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Exit {
+    pub status: Option<ExitStatus>,
+    pub panic: Option<String>,
+}
+
+impl fmt::Display for StuckReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StuckReason::Illegal(reason) => write!(f, "illegal move: {}", reason),
+            StuckReason::Malformed(s) => write!(f, "malformed move: {:?}", s),
+            StuckReason::Timeout(limit) => write!(f, "no move within {:?}", limit),
+            StuckReason::Exited(exit) => {
+                write!(f, "exited before the end of the game")?;
+                if let Some(status) = exit.status {
+                    write!(f, " ({})", status)?;
+                }
+                if let Some(panic) = &exit.panic {
+                    write!(f, " after panicking: {}", panic)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+// The first, and only, failure of a bot in a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stuck {
+    pub turn: usize,
+    pub reason: StuckReason,
+    // Whether the piece could have been placed at all. Every game ends with both bots stuck, and normally it's because they had no legal move left.
+    pub could_move: bool,
+}
+
+impl fmt::Display for Stuck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "turn {}: {}", self.turn, self.reason)?;
+        if !self.could_move {
+            write!(f, " (no legal move was left)")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct MatchReport {
+    pub result: GameResult,
+    pub stuck: [Option<Stuck>; 2],
+    // The end of what each bot wrote to stderr during the game.
+    pub stderr: [String; 2],
+}
+
+// The engine keeps the board from both players' points of view, in step, so that each bot's move can be checked against its own view without building it afresh every turn. The first is player 1's, where player 1's cells are `Own*` and player 2's are `Opponent*`. Both bots are sent the same text, since the characters themselves don't depend on the point of view.
+pub fn run(config: &Config, out: &mut impl Write) -> Result<MatchReport, GameError> {
+    let anfield = map::load(&config.map, 1)?;
+    let mut pieces = match &config.pieces {
+        Some(path) => PieceGenerator::load(path)?,
+        None => PieceGenerator::for_map(&anfield, config.seed),
+    };
+    let swapped = anfield.swapped();
+    let mut views = [anfield, swapped];
+
+    let mut bots = [
+        Bot::spawn(&config.players[0], 1)?,
+        Bot::spawn(&config.players[1], 2)?,
+    ];
+    if !config.quiet {
+        for bot in &bots {
+            writeln!(out, "{}", bot.intro)?;
+        }
+    }
+
+    let mut scoreboard = Scoreboard::new();
+    let mut stuck = [None, None];
+    while !scoreboard.is_over() {
+        for seat in 0..2 {
+            if scoreboard.is_stuck(seat) {
+                continue;
+            }
+
+            let piece = pieces.next_piece();
+            let mut message = String::new();
+            protocol::write_anfield(&mut message, &views[0]);
+            protocol::write_piece(&mut message, &piece);
+            if !config.quiet {
+                out.write_all(message.as_bytes())?;
+            }
+
+            let reply = bots[seat].ask(&message, config.timeout);
+            if !config.quiet {
+                let answer = reply.as_deref().unwrap_or("");
+                writeln!(out, "<got ({}): {}", SEAT_NAMES[seat], answer)?;
+            }
+
+            match judge(reply, &views[seat], &piece) {
+                Ok([x, y]) => {
+                    views[seat].apply(&piece, x, y, Player::Own);
+                    views[1 - seat].apply(&piece, x, y, Player::Opponent);
+                    scoreboard.record_placement(seat);
+                }
+                Err(reason) => {
+                    scoreboard.record_stuck(seat);
+                    let reason = match reason {
+                        StuckReason::Exited(_) => StuckReason::Exited(bots[seat].exit()),
+                        reason => reason,
+                    };
+                    stuck[seat] = Some(Stuck {
+                        turn: scoreboard.result().turns,
+                        reason,
+                        could_move: rules::can_place(&views[seat], &piece),
+                    });
+                }
+            }
+        }
+    }
+
+    if !config.quiet {
+        let mut board = String::new();
+        protocol::write_anfield(&mut board, &views[0]);
+        out.write_all(board.as_bytes())?;
+    }
+    let result = scoreboard.result();
+    writeln!(out, "{}", result)?;
+    out.flush()?;
+
+    let [a, b] = bots;
+    let stderr = [a.finish(), b.finish()];
+    Ok(MatchReport {
+        result,
+        stuck,
+        stderr,
+    })
+}
+
+// `view` is the board from the point of view of the bot that replied.
+fn judge(
+    reply: Result<String, StuckReason>,
+    view: &Anfield,
+    piece: &Piece,
+) -> Result<[i32; 2], StuckReason> {
+    let reply = reply?;
+    let [x, y] = protocol::parse_move(&reply).ok_or(StuckReason::Malformed(reply))?;
+    rules::check_placement(view, piece, x, y).map_err(StuckReason::Illegal)?;
+    Ok([x, y])
+}
+
+// The message of a Rust panic, from what the program wrote to stderr: the line after `thread 'main' panicked at src/x.rs:1:2:`, or the rest of that line in older versions of Rust, followed by where it happened.
+pub fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines();
+    let location = lines.find_map(|line| line.split_once(" panicked at ").map(|(_, rest)| rest))?;
+    match lines.next().filter(|line| !line.starts_with("note:")) {
+        Some(message) => Some(format!("{} at {}", message, location.trim_end_matches(':'))),
+        None => Some(location.to_string()),
+    }
+}
+
+struct Bot {
+    intro: String,
+    child: Child,
+    stdin: Option<ChildStdin>,
+    replies: Receiver<String>,
+    stderr: Arc<Mutex<String>>,
+    stderr_reader: JoinHandle<()>,
+}
+
+impl Bot {
+    fn spawn(path: &Path, id: u8) -> Result<Self, GameError> {
+        let mut child = Command::new(path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| GameError::SpawnBot(id, format!("{}: {}", path.display(), e)))?;
+
+        // Replies are read on their own thread so that the engine only ever waits on a channel, never on the pipe itself.
+        let stdout = child.stdout.take().expect("Bot stdout should be piped");
+        let (tx, replies) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        // Only the end of stderr is kept, since that's where a panic or other last words will be.
+        let mut stderr_pipe = child.stderr.take().expect("Bot stderr should be piped");
+        let stderr = Arc::new(Mutex::new(String::new()));
+        let sink = Arc::clone(&stderr);
+        let stderr_reader = thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(n) = stderr_pipe.read(&mut buffer) {
+                if n == 0 {
+                    break;
+                }
+                let mut text = sink.lock().expect("Stderr lock poisoned");
+                text.push_str(&String::from_utf8_lossy(&buffer[..n]));
+                if text.len() > STDERR_LIMIT {
+                    let mut cut = text.len() - STDERR_LIMIT;
+                    while !text.is_char_boundary(cut) {
+                        cut += 1;
+                    }
+                    text.drain(..cut);
+                }
+            }
+        });
+
+        let stdin = child.stdin.take();
+        let mut bot = Bot {
+            intro: protocol::player_line(id, &path.display().to_string()),
+            child,
+            stdin,
+            replies,
+            stderr,
+            stderr_reader,
+        };
+        let intro = format!("{}\n", bot.intro);
+        bot.send(&intro);
+        Ok(bot)
+    }
+
+    fn send(&mut self, message: &str) -> bool {
+        match &mut self.stdin {
+            Some(stdin) => stdin
+                .write_all(message.as_bytes())
+                .and_then(|_| stdin.flush())
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    fn ask(&mut self, message: &str, timeout: Duration) -> Result<String, StuckReason> {
+        if !self.send(message) {
+            return Err(StuckReason::Exited(Exit::default()));
+        }
+        self.replies.recv_timeout(timeout).map_err(|e| match e {
+            RecvTimeoutError::Timeout => StuckReason::Timeout(timeout),
+            RecvTimeoutError::Disconnected => StuckReason::Exited(Exit::default()),
+        })
+    }
+
+    // Called once the bot has stopped answering. It has most likely exited, or is about to, so give it a moment.
+    fn exit(&mut self) -> Exit {
+        let deadline = Instant::now() + EXIT_GRACE;
+        let status = loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) => break Some(status),
+                Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(5)),
+                _ => break None,
+            }
+        };
+        self.wait_for_stderr(deadline);
+        let panic = panic_message(&self.stderr.lock().expect("Stderr lock poisoned"));
+        Exit { status, panic }
+    }
+
+    fn wait_for_stderr(&self, deadline: Instant) {
+        while !self.stderr_reader.is_finished() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    // Ends the bot and returns what it wrote to stderr. A bot that is still running is killed, as the official engine does, but whatever it has written is kept. Its children may hold on to stderr, so this doesn't wait for the pipe to close for long.
+    fn finish(mut self) -> String {
+        self.stdin.take();
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        self.wait_for_stderr(Instant::now() + EXIT_GRACE);
+        self.stderr.lock().expect("Stderr lock poisoned").clone()
+    }
+}
+
+impl Drop for Bot {
+    fn drop(&mut self) {
+        self.stdin.take();
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_panic_message() {
+        let current = "thread 'main' panicked at src/anfield.rs:40:14:\nInvalid cell\nnote: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(
+            panic_message(current).as_deref(),
+            Some("Invalid cell at src/anfield.rs:40:14")
+        );
+
+        let old = "thread 'main' panicked at 'Invalid cell', src/anfield.rs:40:14\nnote: run with `RUST_BACKTRACE=1`\n";
+        assert_eq!(
+            panic_message(old).as_deref(),
+            Some("'Invalid cell', src/anfield.rs:40:14")
+        );
+
+        assert_eq!(panic_message("some logging\n"), None);
+    }
+}
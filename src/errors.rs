@@ -11,6 +11,16 @@ pub enum GameError {
     ParsePieceDimensions(String),
     ParsePieceBody(String),
     UnexpectedEof(&'static str),
+    ParseMap(String),
+    // The seat of the bot, 1 or 2, and why it couldn't be launched.
+    SpawnBot(u8, String),
+    ParseTranscript(String),
+    ParseRatings(String),
+    MixedContestants(String),
+    ParseParams(String),
+    ParseBook(String),
+    // The phase that was given a phased strategy of its own.
+    NestedPhased(&'static str),
 }
 
 impl std::error::Error for GameError {
@@ -48,6 +58,26 @@ impl fmt::Display for GameError {
             GameError::UnexpectedEof(context) => {
                 write!(f, "Unexpected end of input while parsing {}", context)
             }
+            GameError::ParseMap(s) => write!(f, "Failed to parse map: {:?}", s),
+            GameError::SpawnBot(id, s) => write!(f, "Failed to launch bot p{}: {}", id, s),
+            GameError::ParseTranscript(s) => {
+                write!(f, "Failed to parse transcript move: {:?}", s)
+            }
+            GameError::ParseRatings(s) => write!(f, "Failed to parse rating: {:?}", s),
+            GameError::MixedContestants(s) => {
+                write!(
+                    f,
+                    "Strategies can only play strategies, and bots bots: {}",
+                    s
+                )
+            }
+            GameError::ParseParams(s) => write!(f, "Failed to parse parameter: {:?}", s),
+            GameError::ParseBook(s) => write!(f, "Failed to parse opening book line: {:?}", s),
+            GameError::NestedPhased(phase) => write!(
+                f,
+                "The phased strategy can't be nested: pick another for the {} phase",
+                phase
+            ),
         }
     }
 }
@@ -0,0 +1,225 @@
+// Match results as JSON Lines or CSV, one game per line, for analysis elsewhere. Both formats have the same flat fields, so a JSON record and a CSV row say the same thing.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::{engine::MatchReport, scoring::GameResult};
+
+const FIELDS: [&str; 11] = [
+    "p1",
+    "p2",
+    "map",
+    "seed",
+    "p1_pieces",
+    "p2_pieces",
+    "winner",
+    "p1_stuck_turn",
+    "p2_stuck_turn",
+    "p1_stuck_reason",
+    "p2_stuck_reason",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchRecord {
+    // By seat, player 1 first.
+    pub players: [String; 2],
+    pub map: String,
+    pub seed: u64,
+    pub result: GameResult,
+    // Why each player got stuck, if known, e.g. a crash or a timeout.
+    pub stuck_reasons: [Option<String>; 2],
+}
+
+enum Value {
+    Text(String),
+    Number(u64),
+    Null,
+}
+
+impl MatchRecord {
+    pub fn new(players: [String; 2], map: String, seed: u64, report: &MatchReport) -> Self {
+        MatchRecord {
+            players,
+            map,
+            seed,
+            result: report.result.clone(),
+            stuck_reasons: report
+                .stuck
+                .clone()
+                .map(|stuck| stuck.map(|s| s.reason.to_string())),
+        }
+    }
+
+    fn values(&self) -> [Value; 11] {
+        let text = |s: &str| Value::Text(s.to_string());
+        let number = |n: Option<usize>| n.map_or(Value::Null, |n| Value::Number(n as u64));
+        let [p1_reason, p2_reason] = self
+            .stuck_reasons
+            .clone()
+            .map(|r| r.map_or(Value::Null, Value::Text));
+        [
+            text(&self.players[0]),
+            text(&self.players[1]),
+            text(&self.map),
+            Value::Number(self.seed),
+            number(Some(self.result.scores[0])),
+            number(Some(self.result.scores[1])),
+            number(self.result.winner().map(usize::from)),
+            number(self.result.stuck_turns[0]),
+            number(self.result.stuck_turns[1]),
+            p1_reason,
+            p2_reason,
+        ]
+    }
+
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = FIELDS
+            .iter()
+            .zip(self.values())
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::Text(s) => json_string(&s),
+                    Value::Number(n) => n.to_string(),
+                    Value::Null => "null".to_string(),
+                };
+                format!("\"{}\":{}", name, value)
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    pub fn csv_header() -> String {
+        FIELDS.join(",")
+    }
+
+    pub fn to_csv(&self) -> String {
+        let fields: Vec<String> = self
+            .values()
+            .into_iter()
+            .map(|value| match value {
+                Value::Text(s) => csv_field(&s),
+                Value::Number(n) => n.to_string(),
+                Value::Null => String::new(),
+            })
+            .collect();
+        fields.join(",")
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// Appends records to a JSON Lines file, a CSV file, or both. Files are appended to, so results from several runs can be collected in one place; a CSV header is only written to a new or empty file.
+#[derive(Default)]
+pub struct Exporter {
+    json: Option<File>,
+    csv: Option<File>,
+}
+
+impl Exporter {
+    pub fn new(json: Option<&Path>, csv: Option<&Path>) -> io::Result<Self> {
+        let open = |path: &Path| OpenOptions::new().create(true).append(true).open(path);
+        let json = json.map(open).transpose()?;
+        let mut csv = csv.map(open).transpose()?;
+        if let Some(file) = &mut csv
+            && file.metadata()?.len() == 0
+        {
+            writeln!(file, "{}", MatchRecord::csv_header())?;
+        }
+        Ok(Exporter { json, csv })
+    }
+
+    pub fn write(&mut self, record: &MatchRecord) -> io::Result<()> {
+        if let Some(file) = &mut self.json {
+            writeln!(file, "{}", record.to_json())?;
+        }
+        if let Some(file) = &mut self.csv {
+            writeln!(file, "{}", record.to_csv())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_record() -> MatchRecord {
+        MatchRecord {
+            players: ["bots/max, v2".to_string(), "robots/\"bender\"".to_string()],
+            map: "maps/map00".to_string(),
+            seed: 42,
+            result: GameResult {
+                scores: [10, 7],
+                stuck_turns: [Some(21), Some(14)],
+                turns: 21,
+            },
+            stuck_reasons: [None, Some("exited before the end of the game".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            make_record().to_json(),
+            r#"{"p1":"bots/max, v2","p2":"robots/\"bender\"","map":"maps/map00","seed":42,"p1_pieces":10,"p2_pieces":7,"winner":1,"p1_stuck_turn":21,"p2_stuck_turn":14,"p1_stuck_reason":null,"p2_stuck_reason":"exited before the end of the game"}"#
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let header = MatchRecord::csv_header();
+        let row = make_record().to_csv();
+        assert_eq!(header.split(',').count(), FIELDS.len());
+        assert_eq!(
+            row,
+            r#""bots/max, v2","robots/""bender""",maps/map00,42,10,7,1,21,14,,exited before the end of the game"#
+        );
+    }
+
+    #[test]
+    fn test_exporter_appends_with_one_header() {
+        let dir = std::env::temp_dir();
+        let json = dir.join(format!("filler-export-{}.jsonl", std::process::id()));
+        let csv = dir.join(format!("filler-export-{}.csv", std::process::id()));
+        for _ in 0..2 {
+            let mut exporter = Exporter::new(Some(&json), Some(&csv)).expect("Failed to open");
+            exporter.write(&make_record()).expect("Failed to write");
+        }
+
+        let json_text = std::fs::read_to_string(&json).expect("Failed to read JSON");
+        let csv_text = std::fs::read_to_string(&csv).expect("Failed to read CSV");
+        let _ = std::fs::remove_file(&json);
+        let _ = std::fs::remove_file(&csv);
+
+        assert_eq!(json_text.lines().count(), 2);
+        assert_eq!(csv_text.lines().count(), 3);
+        assert!(csv_text.starts_with("p1,p2,map,seed,"));
+    }
+}
@@ -1,10 +1,16 @@
-use crate::{anfield::Anfield, errors::GameError, parse, piece::Piece, strategy::Strategy};
+use std::time::Instant;
+
+use crate::{
+    anfield::Anfield, errors::GameError, parse, piece::Piece, strategy::Strategy,
+    transcript::Recorder,
+};
 
 pub struct Game<S: Strategy> {
     pub anfield: Anfield,
     pub strategy: S,
     lines: Box<dyn Iterator<Item = Result<String, std::io::Error>>>,
     initializing: bool,
+    recorder: Option<Recorder>,
 }
 
 impl<S: Strategy> Game<S> {
@@ -17,8 +23,14 @@ impl<S: Strategy> Game<S> {
             strategy,
             lines: Box::new(lines),
             initializing: true,
+            recorder: None,
         }
     }
+
+    pub fn with_recorder(mut self, recorder: Recorder) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
 }
 
 impl<S: Strategy> Iterator for Game<S> {
@@ -79,7 +91,18 @@ fn is_eof_error(error: &std::io::Error) -> bool {
 
 impl<S: Strategy> Game<S> {
     pub fn play(&mut self, piece: &Piece) -> String {
+        let start = Instant::now();
         let [x, y] = self.strategy.choose_move(&self.anfield, piece);
+        let elapsed = start.elapsed();
+
+        // A transcript is only a debugging aid, so failing to write one shouldn't cost the game.
+        if let Some(recorder) = &mut self.recorder
+            && let Err(e) = recorder.record(&self.anfield, piece, [x, y], elapsed)
+        {
+            eprintln!("Failed to write transcript, giving up on it: {}", e);
+            self.recorder = None;
+        }
+
         format!("{} {}", x, y)
     }
 }
@@ -142,7 +165,7 @@ Piece 8 2:
 
         let lines = make_lines(input);
         let anfield = Anfield::new(1u8);
-        let strategy = Attack;
+        let strategy = Attack::default();
         let mut game = Game::new(anfield, strategy, lines);
 
         let result = game.next();
@@ -232,7 +255,7 @@ O
 
         let lines = make_lines(input);
         let anfield = Anfield::new(1u8);
-        let strategy = Attack;
+        let strategy = Attack::default();
         let mut game = Game::new(anfield, strategy, lines);
 
         let result = game.next();
@@ -1,10 +1,28 @@
 pub mod anfield;
+pub mod batch;
 pub mod bimap;
+pub mod book;
 pub mod cell;
+pub mod contestant;
+pub mod engine;
 pub mod errors;
+pub mod export;
 pub mod game;
+pub mod map;
 pub mod parse;
 pub mod piece;
+pub mod protocol;
+pub mod ratings;
+pub mod replay;
+pub mod rng;
+pub mod rules;
 pub mod run;
+pub mod scoring;
+pub mod simulate;
+pub mod sprt;
 pub mod strategy;
 pub mod symbols;
+pub mod territory;
+pub mod tournament;
+pub mod transcript;
+pub mod tuning;
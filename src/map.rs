@@ -0,0 +1,123 @@
+// Maps in the format of the official `maps/map00`-`map02`: plain rows of '.', '@' and '$'. For convenience, the loader also accepts a board copied from engine output, with its `Anfield W H:` header, column numbers and row numbers.
+
+pub mod generator;
+
+use std::{fs, path::Path};
+
+use crate::{anfield::Anfield, errors::GameError, symbols::CellRole};
+
+pub fn load(path: &Path, own_id: u8) -> Result<Anfield, GameError> {
+    let text = fs::read_to_string(path)?;
+    parse(&text, own_id).map_err(|e| match e {
+        GameError::ParseMap(msg) => GameError::ParseMap(format!("{}: {}", path.display(), msg)),
+        e => e,
+    })
+}
+
+pub fn parse(text: &str, own_id: u8) -> Result<Anfield, GameError> {
+    let rows: Vec<&str> = text
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.starts_with("Anfield"))
+        .filter(|line| !line.starts_with(' '))
+        .map(strip_row_number)
+        .collect();
+
+    let height = rows.len();
+    let width = rows.first().map_or(0, |row| row.chars().count());
+    if width == 0 {
+        return Err(GameError::ParseMap("map is empty".to_string()));
+    }
+
+    let mut anfield = Anfield::new(own_id);
+    anfield.set_dimensions(width, height);
+    for (y, row) in rows.iter().enumerate() {
+        if row.chars().count() != width {
+            let msg = format!("row {} is not {} cells wide", y, width);
+            return Err(GameError::ParseMap(msg));
+        }
+        for (x, c) in row.chars().enumerate() {
+            let cell_role = anfield.get_role(c).ok_or_else(|| {
+                GameError::ParseMap(format!("unexpected character {:?} at {} {}", c, x, y))
+            })?;
+            anfield.set_cell(x, y, cell_role);
+        }
+    }
+
+    let has = |a: CellRole, b: CellRole| anfield.cells.iter().any(|&c| c == a || c == b);
+    if !has(CellRole::OwnSymbol, CellRole::OwnLatestMove)
+        || !has(CellRole::OpponentSymbol, CellRole::OpponentLatestMove)
+    {
+        return Err(GameError::ParseMap(
+            "map needs a starting cell for each player".to_string(),
+        ));
+    }
+
+    Ok(anfield)
+}
+
+// The inverse of `parse`: plain rows, ready to be written to a map file.
+pub fn render(anfield: &Anfield) -> String {
+    let mut text = String::with_capacity((anfield.width + 1) * anfield.height);
+    for row in anfield.cells.chunks(anfield.width) {
+        text.extend(row.iter().map(|&cell| anfield.get_char(cell)));
+        text.push('\n');
+    }
+    text
+}
+
+fn strip_row_number(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((number, row)) if number.chars().all(|c| c.is_ascii_digit()) => row,
+        _ => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+..........
+.@........
+..........
+........$.
+";
+
+    #[test]
+    fn test_parse_plain_map() {
+        let anfield = parse(MAP, 1).expect("Failed to parse map");
+        assert_eq!((anfield.width, anfield.height), (10, 4));
+        assert_eq!(anfield.get_cell_role(1, 1), Some(CellRole::OwnSymbol));
+        assert_eq!(anfield.get_cell_role(8, 3), Some(CellRole::OpponentSymbol));
+        assert_eq!(render(&anfield), MAP);
+
+        let anfield = parse(MAP, 2).expect("Failed to parse map");
+        assert_eq!(anfield.get_cell_role(1, 1), Some(CellRole::OpponentSymbol));
+        assert_eq!(anfield.get_cell_role(8, 3), Some(CellRole::OwnSymbol));
+    }
+
+    #[test]
+    fn test_parse_engine_board() {
+        let board = "\
+Anfield 10 4:
+    0123456789
+000 ..........
+001 .@........
+002 ..........
+003 ........$.
+";
+        let anfield = parse(board, 1).expect("Failed to parse board");
+        assert_eq!(render(&anfield), MAP);
+    }
+
+    #[test]
+    fn test_parse_map_errors() {
+        for bad in ["", "...\n..\n", ".@.\n...\n", ".@.\n.x$\n"] {
+            match parse(bad, 1) {
+                Err(GameError::ParseMap(_)) => {}
+                other => panic!("Expected ParseMap error for {:?}, got {:?}", bad, other),
+            }
+        }
+    }
+}
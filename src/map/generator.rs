@@ -0,0 +1,135 @@
+use crate::{anfield::Anfield, cell::Cell, rng::Rng, symbols::CellRole};
+
+#[derive(Debug, Clone, Copy)]
+pub enum StartPositions {
+    // Both starts anywhere, at least `min_distance` apart (taxicab).
+    Random { min_distance: usize },
+    // Player 1 anywhere, player 2 at the point reflection through the centre, as on the official maps. Both sides then face exactly the same board.
+    Mirrored { min_distance: usize },
+    Fixed([Cell; 2]),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MapOptions {
+    pub width: usize,
+    pub height: usize,
+    pub starts: StartPositions,
+}
+
+impl MapOptions {
+    pub fn new(width: usize, height: usize) -> Self {
+        MapOptions {
+            width,
+            height,
+            starts: StartPositions::Mirrored {
+                min_distance: (width + height) / 2,
+            },
+        }
+    }
+}
+
+// An empty board with one starting cell for each player. The same options and seed always give the same map.
+pub fn generate(options: &MapOptions, own_id: u8, seed: u64) -> Anfield {
+    let MapOptions { width, height, .. } = *options;
+    assert!(width * height >= 2, "Map must have room for two players");
+
+    let mut rng = Rng::new(seed);
+    let random_cell = |rng: &mut Rng| Cell {
+        x: rng.below(width),
+        y: rng.below(height),
+    };
+    let mirror = |c: Cell| Cell {
+        x: width - 1 - c.x,
+        y: height - 1 - c.y,
+    };
+
+    // Give up on the distance after a while rather than loop forever when it can't be met.
+    let mut attempts = 0;
+    let mut accept = |a: Cell, b: Cell, min_distance: usize| {
+        attempts += 1;
+        a != b && (distance(a, b) >= min_distance || attempts > 1000)
+    };
+
+    let [p1, p2] = match options.starts {
+        StartPositions::Fixed(starts) => starts,
+        StartPositions::Random { min_distance } => loop {
+            let (a, b) = (random_cell(&mut rng), random_cell(&mut rng));
+            if accept(a, b, min_distance) {
+                break [a, b];
+            }
+        },
+        StartPositions::Mirrored { min_distance } => loop {
+            let a = random_cell(&mut rng);
+            if accept(a, mirror(a), min_distance) {
+                break [a, mirror(a)];
+            }
+        },
+    };
+
+    let mut anfield = Anfield::new(own_id);
+    anfield.set_dimensions(width, height);
+    let [own, opponent] = if own_id == 1 { [p1, p2] } else { [p2, p1] };
+    anfield.set_cell(own.x, own.y, CellRole::OwnSymbol);
+    anfield.set_cell(opponent.x, opponent.y, CellRole::OpponentSymbol);
+    anfield
+}
+
+fn distance(a: Cell, b: Cell) -> usize {
+    a.x.abs_diff(b.x) + a.y.abs_diff(b.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    fn find(anfield: &Anfield, role: CellRole) -> Vec<Cell> {
+        (0..anfield.cells.len())
+            .filter(|&i| anfield.cells[i] == role)
+            .map(|i| Cell {
+                x: i % anfield.width,
+                y: i / anfield.width,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_generate_is_seeded() {
+        let options = MapOptions::new(40, 30);
+        let a = map::render(&generate(&options, 1, 99));
+        let b = map::render(&generate(&options, 1, 99));
+        assert_eq!(a, b);
+        assert_eq!(map::parse(&a, 1).map(|m| m.cells.len()).ok(), Some(1200));
+    }
+
+    #[test]
+    fn test_generate_mirrored() {
+        let options = MapOptions::new(20, 15);
+        for seed in 0..20 {
+            let anfield = generate(&options, 1, seed);
+            let own = find(&anfield, CellRole::OwnSymbol);
+            let opponent = find(&anfield, CellRole::OpponentSymbol);
+            assert_eq!(own.len(), 1);
+            assert_eq!(
+                opponent,
+                vec![Cell {
+                    x: 19 - own[0].x,
+                    y: 14 - own[0].y
+                }]
+            );
+            assert!(distance(own[0], opponent[0]) >= 17);
+        }
+    }
+
+    #[test]
+    fn test_generate_fixed_from_either_seat() {
+        let starts = [Cell { x: 1, y: 2 }, Cell { x: 8, y: 3 }];
+        let options = MapOptions {
+            starts: StartPositions::Fixed(starts),
+            ..MapOptions::new(10, 5)
+        };
+        let p2_view = generate(&options, 2, 0);
+        assert_eq!(find(&p2_view, CellRole::OwnSymbol), vec![starts[1]]);
+        assert_eq!(find(&p2_view, CellRole::OpponentSymbol), vec![starts[0]]);
+    }
+}
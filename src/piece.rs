@@ -1,8 +1,10 @@
+pub mod generator;
+
 use std::io;
 
 use crate::{cell::Cell, parse, symbols};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Piece {
     pub width: usize,
     pub height: usize,
@@ -0,0 +1,259 @@
+use std::{fs, path::Path};
+
+use crate::{
+    anfield::Anfield, cell::Cell, errors::GameError, parse, piece::Piece, protocol, rng::Rng,
+};
+
+pub struct PieceGenerator {
+    source: Source,
+}
+
+enum Source {
+    Random { rng: Rng, max_side: usize },
+    Replay { pieces: Vec<Piece>, next: usize },
+}
+
+impl PieceGenerator {
+    // Random connected shapes of at least two cells, in rectangles of up to `max_side` by `max_side`. Two cells is the minimum that guarantees every legal move claims at least one new cell, so a game always ends.
+    pub fn new(seed: u64, max_side: usize) -> Self {
+        assert!(max_side >= 2, "Pieces need room for at least two cells");
+        PieceGenerator {
+            source: Source::Random {
+                rng: Rng::new(seed),
+                max_side,
+            },
+        }
+    }
+
+    // Piece sizes scale with the board: up to 4 on `map00` and 8 on `map01` and `map02`. The official engine sends 8-wide pieces on `map01`, so that's the least its bound can be (see `OFFICIAL_PIECES` in the tests).
+    pub fn for_map(anfield: &Anfield, seed: u64) -> Self {
+        let max_side = (anfield.width.min(anfield.height) / 4 + 1).clamp(3, 8);
+        Self::new(seed, max_side)
+    }
+
+    // Deals `pieces` in order, starting again from the first when they run out.
+    pub fn replay(pieces: Vec<Piece>) -> Self {
+        assert!(!pieces.is_empty(), "Nothing to replay");
+        PieceGenerator {
+            source: Source::Replay { pieces, next: 0 },
+        }
+    }
+
+    // A file of pieces in the format the engine sends them, `Piece W H:` followed by the rows, one after another.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        let text = fs::read_to_string(path)?;
+        let mut lines = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(line.to_string()));
+
+        let mut pieces = Vec::new();
+        while let Some(Ok(header)) = lines.next() {
+            let [width, height] = parse::get_width_and_height(header.clone())
+                .map_err(|_| GameError::ParsePieceHeader(header))?;
+            let piece = Piece::new(&mut lines, width, height)
+                .map_err(|e| GameError::ParsePieceBody(e.to_string()))?;
+            pieces.push(piece);
+        }
+
+        if pieces.is_empty() {
+            let msg = format!("no pieces in {}", path.display());
+            return Err(GameError::ParsePieceHeader(msg));
+        }
+        Ok(Self::replay(pieces))
+    }
+
+    pub fn next_piece(&mut self) -> Piece {
+        match &mut self.source {
+            Source::Random { rng, max_side } => random_piece(rng, *max_side),
+            Source::Replay { pieces, next } => {
+                let piece = pieces[*next].clone();
+                *next = (*next + 1) % pieces.len();
+                piece
+            }
+        }
+    }
+}
+
+impl Iterator for PieceGenerator {
+    type Item = Piece;
+
+    fn next(&mut self) -> Option<Piece> {
+        Some(self.next_piece())
+    }
+}
+
+// Grows a shape one neighbouring cell at a time inside a random rectangle. The shape covers at most a third of its rectangle, which matches how sparse the official engine's pieces are, and so, as with those, there are usually empty rows or columns at the edges.
+fn random_piece(rng: &mut Rng, max_side: usize) -> Piece {
+    let width = rng.range(1, max_side);
+    let height = if width == 1 {
+        rng.range(2, max_side)
+    } else {
+        rng.range(1, max_side)
+    };
+    let target = rng.range(2, (width * height / 3).clamp(2, 2 * max_side));
+
+    let mut shape = vec![Cell {
+        x: rng.below(width),
+        y: rng.below(height),
+    }];
+    while shape.len() < target {
+        let Cell { x, y } = shape[rng.below(shape.len())];
+        let candidate = match rng.below(4) {
+            0 if x > 0 => Cell { x: x - 1, y },
+            1 if x + 1 < width => Cell { x: x + 1, y },
+            2 if y > 0 => Cell { x, y: y - 1 },
+            3 if y + 1 < height => Cell { x, y: y + 1 },
+            _ => continue,
+        };
+        if !shape.contains(&candidate) {
+            shape.push(candidate);
+        }
+    }
+    shape.sort_by_key(|cell| (cell.y, cell.x));
+
+    Piece {
+        width,
+        height,
+        shape,
+    }
+}
+
+// Pieces as the engine would send them, for saving sequences to replay later.
+pub fn render(pieces: &[Piece]) -> String {
+    let mut text = String::new();
+    for piece in pieces {
+        protocol::write_piece(&mut text, piece);
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_connected(piece: &Piece) -> bool {
+        let mut reached = vec![false; piece.shape.len()];
+        let mut stack = vec![0];
+        reached[0] = true;
+        while let Some(i) = stack.pop() {
+            let a = piece.shape[i];
+            for (j, b) in piece.shape.iter().enumerate() {
+                if !reached[j] && a.x.abs_diff(b.x) + a.y.abs_diff(b.y) == 1 {
+                    reached[j] = true;
+                    stack.push(j);
+                }
+            }
+        }
+        reached.iter().all(|&r| r)
+    }
+
+    #[test]
+    fn test_random_pieces_are_seeded_connected_and_in_bounds() {
+        let a: Vec<Piece> = PieceGenerator::new(5, 6).take(200).collect();
+        let b: Vec<Piece> = PieceGenerator::new(5, 6).take(200).collect();
+        assert_eq!(render(&a), render(&b));
+
+        for piece in &a {
+            assert!(piece.width <= 6 && piece.height <= 6);
+            assert!(piece.shape.len() >= 2);
+            assert!(
+                piece
+                    .shape
+                    .iter()
+                    .all(|c| c.x < piece.width && c.y < piece.height)
+            );
+            assert!(is_connected(piece), "Disconnected piece: {:?}", piece);
+        }
+    }
+
+    // Pieces sent by the official engine: the three examples in the instructions, and one from its output on `map01`.
+    const OFFICIAL_PIECES: &str = "\
+Piece 2 2:
+.O
+O.
+Piece 5 4:
+.OO..
+.OO..
+..O..
+...O.
+Piece 6 3:
+.OO...
+OOO...
+O..O..
+Piece 8 2:
+....O...
+....O...
+";
+
+    // Average cells per piece, average share of its rectangle that a piece covers, and the share of pieces with an empty row or column at an edge.
+    fn stats(pieces: &[Piece]) -> [f64; 3] {
+        let n = pieces.len() as f64;
+        let padded = |piece: &Piece| {
+            let used_column = |x| piece.shape.iter().any(|c| c.x == x);
+            let used_row = |y| piece.shape.iter().any(|c| c.y == y);
+            !used_column(0)
+                || !used_column(piece.width - 1)
+                || !used_row(0)
+                || !used_row(piece.height - 1)
+        };
+        [
+            pieces.iter().map(|p| p.shape.len() as f64).sum::<f64>() / n,
+            pieces
+                .iter()
+                .map(|p| p.shape.len() as f64 / (p.width * p.height) as f64)
+                .sum::<f64>()
+                / n,
+            pieces.iter().filter(|p| padded(p)).count() as f64 / n,
+        ]
+    }
+
+    #[test]
+    fn test_sizes_match_the_official_pieces() {
+        let path = std::env::temp_dir().join(format!("filler-official-{}", std::process::id()));
+        fs::write(&path, OFFICIAL_PIECES).expect("Failed to write pieces");
+        let official: Vec<Piece> = PieceGenerator::load(&path)
+            .expect("Failed to load pieces")
+            .take(4)
+            .collect();
+        let _ = fs::remove_file(&path);
+
+        let map01 =
+            crate::map::generator::generate(&crate::map::generator::MapOptions::new(40, 30), 1, 1);
+        let generated: Vec<Piece> = PieceGenerator::for_map(&map01, 7).take(4000).collect();
+        assert!(generated.iter().any(|p| p.width == 8 && p.height == 2));
+
+        // Four pieces are a small sample, so the margins are wide.
+        let [cells, fill, padded] = stats(&official);
+        let [g_cells, g_fill, g_padded] = stats(&generated);
+        assert!(
+            (g_cells - cells).abs() < 1.0,
+            "{} cells, not {}",
+            g_cells,
+            cells
+        );
+        assert!((g_fill - fill).abs() < 0.1, "{} full, not {}", g_fill, fill);
+        assert!(
+            (g_padded - padded).abs() < 0.25,
+            "{} padded, not {}",
+            g_padded,
+            padded
+        );
+    }
+
+    #[test]
+    fn test_replay_round_trips_through_file() {
+        let pieces: Vec<Piece> = PieceGenerator::new(11, 4).take(5).collect();
+        let path = std::env::temp_dir().join(format!("filler-pieces-{}", std::process::id()));
+        fs::write(&path, render(&pieces)).expect("Failed to write pieces");
+
+        let replayed: Vec<Piece> = PieceGenerator::load(&path)
+            .expect("Failed to load pieces")
+            .take(10)
+            .collect();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(render(&replayed[..5]), render(&pieces));
+        assert_eq!(render(&replayed[5..]), render(&pieces));
+    }
+}
@@ -0,0 +1,97 @@
+// The text format spoken between the game engine and the bots: the `$$$ exec` line that tells a bot its seat, the Anfield and Piece blocks it receives each turn, and the `x y` line it answers with.
+
+use std::fmt::Write;
+
+use crate::{anfield::Anfield, piece::Piece, symbols};
+
+pub fn player_line(id: u8, path: &str) -> String {
+    format!("$$$ exec p{} : [{}]", id, path)
+}
+
+pub fn write_anfield(out: &mut String, anfield: &Anfield) {
+    let _ = writeln!(out, "Anfield {} {}:", anfield.width, anfield.height);
+
+    out.push_str("    ");
+    for x in 0..anfield.width {
+        let _ = write!(out, "{}", x % 10);
+    }
+    out.push('\n');
+
+    for y in 0..anfield.height {
+        let _ = write!(out, "{:03} ", y);
+        for x in 0..anfield.width {
+            out.push(anfield.get_char(anfield.cells[y * anfield.width + x]));
+        }
+        out.push('\n');
+    }
+}
+
+pub fn write_piece(out: &mut String, piece: &Piece) {
+    let _ = writeln!(out, "Piece {} {}:", piece.width, piece.height);
+
+    let mut rows = vec![vec![symbols::EMPTY_CHAR; piece.width]; piece.height];
+    for cell in &piece.shape {
+        rows[cell.y][cell.x] = symbols::NEW_PIECE_CHAR;
+    }
+    for row in rows {
+        out.extend(row);
+        out.push('\n');
+    }
+}
+
+pub fn parse_move(line: &str) -> Option<[i32; 2]> {
+    let mut parts = line.split_whitespace();
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some([x, y])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, symbols::CellRole};
+
+    #[test]
+    fn test_write_anfield_round_trips_through_parse() {
+        let mut anfield = Anfield::new(1);
+        anfield.set_dimensions(12, 3);
+        anfield.set_cell(1, 0, CellRole::OwnSymbol);
+        anfield.set_cell(10, 2, CellRole::OpponentLatestMove);
+
+        let mut text = String::new();
+        write_anfield(&mut text, &anfield);
+        assert!(text.starts_with("Anfield 12 3:\n    012345678901\n000 .@"));
+
+        let mut lines = text.lines().skip(1).map(|l| Ok(l.to_string()));
+        let mut parsed = Anfield::new(1);
+        parsed.set_dimensions(12, 3);
+        parsed
+            .parse(&mut lines)
+            .expect("Failed to parse written Anfield");
+        assert_eq!(parsed.cells, anfield.cells);
+    }
+
+    #[test]
+    fn test_write_piece_keeps_padding() {
+        let piece = Piece {
+            width: 3,
+            height: 2,
+            shape: vec![Cell { x: 1, y: 1 }, Cell { x: 2, y: 1 }],
+        };
+        let mut text = String::new();
+        write_piece(&mut text, &piece);
+        assert_eq!(text, "Piece 3 2:\n...\n.OO\n");
+    }
+
+    #[test]
+    fn test_parse_move() {
+        assert_eq!(parse_move("7 2"), Some([7, 2]));
+        assert_eq!(parse_move("-1 4\n"), Some([-1, 4]));
+        assert_eq!(parse_move("7"), None);
+        assert_eq!(parse_move("7 2 1"), None);
+        assert_eq!(parse_move("x y"), None);
+    }
+}
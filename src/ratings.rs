@@ -0,0 +1,276 @@
+// A ledger of Glicko-2 ratings (http://www.glicko.net/glicko/glicko2.pdf), kept in a text file with one bot per line: rating, rating deviation, volatility, games played, name.
+
+use std::{collections::BTreeMap, f64::consts::PI, fmt, fs, io, path::Path};
+
+use crate::errors::GameError;
+
+const SCALE: f64 = 173.7178;
+const INITIAL_RATING: f64 = 1500.0;
+const INITIAL_DEVIATION: f64 = 350.0;
+const INITIAL_VOLATILITY: f64 = 0.06;
+// How much the volatility may change. Glickman suggests 0.3 to 1.2.
+const TAU: f64 = 0.5;
+const EPSILON: f64 = 0.000001;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+    pub games: usize,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            rating: INITIAL_RATING,
+            deviation: INITIAL_DEVIATION,
+            volatility: INITIAL_VOLATILITY,
+            games: 0,
+        }
+    }
+}
+
+impl Rating {
+    // A 95% confidence interval for the true rating.
+    pub fn interval(&self) -> [f64; 2] {
+        [
+            self.rating - 1.96 * self.deviation,
+            self.rating + 1.96 * self.deviation,
+        ]
+    }
+
+    // The rating after one rating period against `results`: each an opponent's rating (before the period) and the score against them, 1 for a win, 0.5 for a draw, 0 for a loss.
+    pub fn updated(&self, results: &[(Rating, f64)]) -> Rating {
+        let mu = (self.rating - INITIAL_RATING) / SCALE;
+        let phi = self.deviation / SCALE;
+
+        if results.is_empty() {
+            let phi = (phi * phi + self.volatility * self.volatility).sqrt();
+            return Rating {
+                deviation: phi * SCALE,
+                ..*self
+            };
+        }
+
+        let mut v_inverse = 0.0;
+        let mut improvement = 0.0;
+        for (opponent, score) in results {
+            let mu_j = (opponent.rating - INITIAL_RATING) / SCALE;
+            let g = g(opponent.deviation / SCALE);
+            let e = 1.0 / (1.0 + (-g * (mu - mu_j)).exp());
+            v_inverse += g * g * e * (1.0 - e);
+            improvement += g * (score - e);
+        }
+        let v = 1.0 / v_inverse;
+        let delta = v * improvement;
+
+        let volatility = new_volatility(phi, v, delta, self.volatility);
+        let phi_star = (phi * phi + volatility * volatility).sqrt();
+        let phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+        let mu = mu + phi * phi * improvement;
+
+        Rating {
+            rating: mu * SCALE + INITIAL_RATING,
+            deviation: phi * SCALE,
+            volatility,
+            games: self.games + results.len(),
+        }
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+// Step 5 of the paper: the Illinois algorithm.
+fn new_volatility(phi: f64, v: f64, delta: f64, sigma: f64) -> f64 {
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        let d = phi * phi + v + ex;
+        ex * (delta * delta - phi * phi - v - ex) / (2.0 * d * d) - (x - a) / (TAU * TAU)
+    };
+
+    let mut big_a = a;
+    let mut big_b = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * TAU
+    };
+
+    let mut f_a = f(big_a);
+    let mut f_b = f(big_b);
+    while (big_b - big_a).abs() > EPSILON {
+        let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(big_c);
+        if f_c * f_b <= 0.0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        big_b = big_c;
+        f_b = f_c;
+    }
+
+    (big_a / 2.0).exp()
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+    pub ratings: BTreeMap<String, Rating>,
+}
+
+impl Ledger {
+    // A missing file is an empty ledger.
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, GameError> {
+        let mut ledger = Self::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let bad_line = || GameError::ParseRatings(line.to_string());
+            let mut parts = line.splitn(5, ' ');
+            let mut number = || parts.next().and_then(|s| s.parse::<f64>().ok());
+            let rating = Rating {
+                rating: number().ok_or_else(bad_line)?,
+                deviation: number().ok_or_else(bad_line)?,
+                volatility: number().ok_or_else(bad_line)?,
+                games: number().ok_or_else(bad_line)? as usize,
+            };
+            let name = parts.next().ok_or_else(bad_line)?;
+            ledger.ratings.insert(name.to_string(), rating);
+        }
+        Ok(ledger)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut text = String::new();
+        for (name, r) in &self.ratings {
+            text.push_str(&format!(
+                "{:.4} {:.4} {:.6} {} {}\n",
+                r.rating, r.deviation, r.volatility, r.games, name
+            ));
+        }
+        fs::write(path, text)
+    }
+
+    // One game, `score` being from `a`'s point of view. Each game is its own rating period, so both ratings move straight away.
+    pub fn record(&mut self, a: &str, b: &str, score: f64) {
+        let rating_a = self.ratings.get(a).copied().unwrap_or_default();
+        let rating_b = self.ratings.get(b).copied().unwrap_or_default();
+        self.ratings
+            .insert(a.to_string(), rating_a.updated(&[(rating_b, score)]));
+        self.ratings
+            .insert(b.to_string(), rating_b.updated(&[(rating_a, 1.0 - score)]));
+    }
+
+    pub fn leaderboard(&self) -> Vec<(&str, &Rating)> {
+        let mut entries: Vec<_> = self.ratings.iter().map(|(n, r)| (n.as_str(), r)).collect();
+        entries.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating));
+        entries
+    }
+}
+
+impl fmt::Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .ratings
+            .keys()
+            .map(String::len)
+            .max()
+            .unwrap_or(0)
+            .max(3);
+        writeln!(
+            f,
+            "{:width$} {:>7} {:>17} {:>6}",
+            "bot", "rating", "95% interval", "games"
+        )?;
+        for (name, r) in self.leaderboard() {
+            let [low, high] = r.interval();
+            writeln!(
+                f,
+                "{:width$} {:>7.0} {:>8.0}..{:<7.0} {:>6}",
+                name, r.rating, low, high, r.games
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(rating: f64, deviation: f64) -> Rating {
+        Rating {
+            rating,
+            deviation,
+            ..Rating::default()
+        }
+    }
+
+    // The worked example from Glickman's paper.
+    #[test]
+    fn test_glicko2_example() {
+        let player = rating(1500.0, 200.0);
+        let results = [
+            (rating(1400.0, 30.0), 1.0),
+            (rating(1550.0, 100.0), 0.0),
+            (rating(1700.0, 300.0), 0.0),
+        ];
+        let updated = player.updated(&results);
+        assert!((updated.rating - 1464.06).abs() < 0.01, "{:?}", updated);
+        assert!((updated.deviation - 151.52).abs() < 0.01, "{:?}", updated);
+        assert!(
+            (updated.volatility - 0.05999).abs() < 0.00001,
+            "{:?}",
+            updated
+        );
+        assert_eq!(updated.games, 3);
+    }
+
+    #[test]
+    fn test_ledger_record_and_round_trip() {
+        let mut ledger = Ledger::default();
+        for _ in 0..5 {
+            ledger.record("maximilian v2", "terminator", 1.0);
+        }
+        ledger.record("maximilian v2", "bender", 0.5);
+
+        let board = ledger.leaderboard();
+        assert_eq!(board[0].0, "maximilian v2");
+        assert_eq!(board[0].1.games, 6);
+        assert!(board[0].1.deviation < INITIAL_DEVIATION);
+        assert!(ledger.ratings["terminator"].rating < INITIAL_RATING);
+
+        let path = std::env::temp_dir().join(format!("filler-ratings-{}", std::process::id()));
+        ledger.save(&path).expect("Failed to save ledger");
+        let loaded = Ledger::load(&path).expect("Failed to load ledger");
+        let _ = fs::remove_file(&path);
+        assert_eq!(loaded.ratings.len(), 3);
+        assert!((loaded.ratings["bender"].rating - ledger.ratings["bender"].rating).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_lines() {
+        assert!(matches!(
+            Ledger::parse("1500 350 x 0 a"),
+            Err(GameError::ParseRatings(_))
+        ));
+        assert!(matches!(
+            Ledger::parse("1500 350 0.06 0"),
+            Err(GameError::ParseRatings(_))
+        ));
+    }
+}
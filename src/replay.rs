@@ -0,0 +1,119 @@
+// Feeds the turns of a recorded game to a strategy and compares its moves with the ones that were recorded.
+
+use std::fmt;
+
+use crate::{
+    rules::{self, IllegalMove},
+    strategy::Strategy,
+    transcript::Transcript,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnReport {
+    // Counting the bot's own turns from 1.
+    pub turn: usize,
+    pub recorded: [i32; 2],
+    pub replayed: [i32; 2],
+    pub illegal: Option<IllegalMove>,
+    // No legal move existed, so an illegal one was unavoidable.
+    pub stuck: bool,
+}
+
+impl TurnReport {
+    pub fn differs(&self) -> bool {
+        self.recorded != self.replayed
+    }
+
+    pub fn is_flagged(&self) -> bool {
+        self.differs() || (self.illegal.is_some() && !self.stuck)
+    }
+}
+
+impl fmt::Display for TurnReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [rx, ry] = self.recorded;
+        let [nx, ny] = self.replayed;
+        write!(
+            f,
+            "{:>5}  {:>4} {:<4} {:>4} {:<4}",
+            self.turn, rx, ry, nx, ny
+        )?;
+        if self.differs() {
+            write!(f, " changed")?;
+        }
+        if self.stuck {
+            write!(f, " stuck")?;
+        } else if let Some(reason) = self.illegal {
+            write!(f, " illegal: {}", reason)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn replay(transcript: &Transcript, strategy: &impl Strategy) -> Vec<TurnReport> {
+    transcript
+        .turns
+        .iter()
+        .enumerate()
+        .map(|(i, turn)| {
+            let replayed = strategy.choose_move(&turn.anfield, &turn.piece);
+            let [x, y] = replayed;
+            TurnReport {
+                turn: i + 1,
+                recorded: turn.chosen,
+                replayed,
+                illegal: rules::check_placement(&turn.anfield, &turn.piece, x, y).err(),
+                stuck: !rules::can_place(&turn.anfield, &turn.piece),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        anfield::Anfield,
+        map,
+        piece::{Piece, generator::PieceGenerator},
+        strategy::attack::Attack,
+        transcript::Turn,
+    };
+    use std::time::Duration;
+
+    struct OffBoard;
+
+    impl Strategy for OffBoard {
+        fn choose_move(&self, anfield: &Anfield, _piece: &Piece) -> [i32; 2] {
+            [anfield.width as i32, 0]
+        }
+    }
+
+    #[test]
+    fn test_replay_flags_changed_and_illegal_moves() {
+        let anfield = map::parse("..........\n.@........\n.........$\n", 1).expect("Bad map");
+        let turns = PieceGenerator::new(4, 3)
+            .take(3)
+            .map(|piece| Turn {
+                chosen: Attack::default().choose_move(&anfield, &piece),
+                anfield: anfield.clone(),
+                piece,
+                elapsed: Duration::ZERO,
+            })
+            .collect();
+        let transcript = Transcript { own_id: 1, turns };
+
+        let same = replay(&transcript, &Attack::default());
+        assert_eq!(same.len(), 3);
+        assert!(same.iter().all(|t| !t.is_flagged()), "{:?}", same);
+
+        let different = replay(&transcript, &OffBoard);
+        assert!(different.iter().all(TurnReport::differs));
+        assert!(
+            different
+                .iter()
+                .all(|t| t.illegal == Some(IllegalMove::OutOfBounds))
+        );
+        assert!(different[0].to_string().starts_with("    1"));
+    }
+}
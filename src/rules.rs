@@ -0,0 +1,145 @@
+use std::fmt;
+
+use crate::{anfield::Anfield, piece::Piece, symbols::CellRole};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalMove {
+    OutOfBounds,
+    CoversOpponent,
+    NoOverlap,
+    MultipleOverlaps { count: usize },
+}
+
+impl std::error::Error for IllegalMove {}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IllegalMove::OutOfBounds => write!(f, "piece extends outside the Anfield"),
+            IllegalMove::CoversOpponent => write!(f, "piece covers opponent territory"),
+            IllegalMove::NoOverlap => write!(f, "piece does not overlap own territory"),
+            IllegalMove::MultipleOverlaps { count } => {
+                write!(f, "piece overlaps own territory {} times", count)
+            }
+        }
+    }
+}
+
+// Checks a move from the point of view of the Anfield's own player. `x` and `y` are the coordinates of the top-left corner of the piece, which may be negative as long as every shape cell lands inside the Anfield.
+pub fn check_placement(
+    anfield: &Anfield,
+    piece: &Piece,
+    x: i32,
+    y: i32,
+) -> Result<(), IllegalMove> {
+    let mut overlaps_with_own_territory = 0;
+    for cell in &piece.shape {
+        let s = x as i64 + cell.x as i64;
+        let t = y as i64 + cell.y as i64;
+        if s < 0 || t < 0 {
+            return Err(IllegalMove::OutOfBounds);
+        }
+
+        match anfield.get_cell_role(s as usize, t as usize) {
+            None => return Err(IllegalMove::OutOfBounds),
+            Some(CellRole::OpponentSymbol | CellRole::OpponentLatestMove) => {
+                return Err(IllegalMove::CoversOpponent);
+            }
+            Some(CellRole::OwnSymbol | CellRole::OwnLatestMove) => overlaps_with_own_territory += 1,
+            Some(CellRole::Empty) => (),
+        }
+    }
+
+    match overlaps_with_own_territory {
+        0 => Err(IllegalMove::NoOverlap),
+        1 => Ok(()),
+        count => Err(IllegalMove::MultipleOverlaps { count }),
+    }
+}
+
+// Every legal position for the piece, column by column. Top-left corners range from one piece-width left of (and one piece-height above) the Anfield, since only the shape cells need to land inside it.
+pub fn legal_moves_iter<'a>(
+    anfield: &'a Anfield,
+    piece: &'a Piece,
+) -> impl Iterator<Item = [i32; 2]> + 'a {
+    (-(piece.width as i32)..anfield.width as i32)
+        .flat_map(move |x| (-(piece.height as i32)..anfield.height as i32).map(move |y| [x, y]))
+        .filter(move |&[x, y]| check_placement(anfield, piece, x, y).is_ok())
+}
+
+pub fn legal_moves(anfield: &Anfield, piece: &Piece) -> Vec<[i32; 2]> {
+    legal_moves_iter(anfield, piece).collect()
+}
+
+// Whether the Anfield's own player can place the piece at all. When this is false, the player is stuck.
+pub fn can_place(anfield: &Anfield, piece: &Piece) -> bool {
+    legal_moves_iter(anfield, piece).next().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cell::Cell;
+
+    // Own cell at (1, 1), opponent cell at (3, 1).
+    fn make_anfield() -> Anfield {
+        let mut anfield = Anfield::new(1);
+        anfield.set_dimensions(5, 3);
+        anfield.set_cell(1, 1, CellRole::OwnSymbol);
+        anfield.set_cell(2, 1, CellRole::OwnLatestMove);
+        anfield.set_cell(3, 1, CellRole::OpponentSymbol);
+        anfield
+    }
+
+    fn make_bar() -> Piece {
+        Piece {
+            width: 3,
+            height: 1,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }],
+        }
+    }
+
+    #[test]
+    fn test_check_placement() {
+        let anfield = make_anfield();
+        let piece = make_bar();
+
+        assert_eq!(check_placement(&anfield, &piece, 0, 1), Ok(()));
+        assert_eq!(
+            check_placement(&anfield, &piece, 1, 0),
+            Err(IllegalMove::NoOverlap)
+        );
+        assert_eq!(
+            check_placement(&anfield, &piece, 1, 1),
+            Err(IllegalMove::MultipleOverlaps { count: 2 })
+        );
+        assert_eq!(
+            check_placement(&anfield, &piece, 2, 1),
+            Err(IllegalMove::CoversOpponent)
+        );
+        assert_eq!(
+            check_placement(&anfield, &piece, 4, 1),
+            Err(IllegalMove::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_check_placement_allows_corner_outside_anfield() {
+        let anfield = make_anfield();
+        let piece = Piece {
+            width: 2,
+            height: 2,
+            shape: vec![Cell { x: 1, y: 1 }],
+        };
+
+        assert_eq!(check_placement(&anfield, &piece, 0, 0), Ok(()));
+        assert_eq!(
+            check_placement(&anfield, &piece, -1, -1),
+            Err(IllegalMove::NoOverlap)
+        );
+        assert_eq!(
+            check_placement(&anfield, &piece, -2, 0),
+            Err(IllegalMove::OutOfBounds)
+        );
+    }
+}
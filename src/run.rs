@@ -1,15 +1,48 @@
-use std::io::{self, BufRead, Write};
+use std::{
+    env,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
 
-use crate::{anfield::Anfield, errors::GameError, game::Game, parse, strategy::Strategy};
+use crate::{
+    anfield::Anfield, errors::GameError, game::Game, parse, strategy::Strategy,
+    transcript::Recorder,
+};
+
+// If set, the bot writes a transcript of the game to this path (see `transcript`).
+pub const TRANSCRIPT_VAR: &str = "FILLER_TRANSCRIPT";
 
 pub fn run(strategy: impl Strategy) -> Result<(), GameError> {
+    let transcript = env::var_os(TRANSCRIPT_VAR).map(PathBuf::from);
+    run_with_transcript(strategy, transcript)
+}
+
+pub fn run_with_transcript(
+    strategy: impl Strategy,
+    transcript: Option<PathBuf>,
+) -> Result<(), GameError> {
     let stdin = io::stdin();
     let mut lines = stdin.lock().lines();
 
     let first_line = parse::read_line(&mut lines, "first")?;
+    // As in `Game::play`, a transcript that can't be written is dropped rather than costing the game.
+    let recorder = transcript.and_then(|path| match Recorder::create(&path, &first_line) {
+        Ok(recorder) => Some(recorder),
+        Err(e) => {
+            eprintln!(
+                "Failed to create transcript {}, playing without it: {}",
+                path.display(),
+                e
+            );
+            None
+        }
+    });
     let own_id = parse::get_ids(first_line)?;
     let anfield = Anfield::new(own_id);
-    let game = Game::new(anfield, strategy, lines);
+    let mut game = Game::new(anfield, strategy, lines);
+    if let Some(recorder) = recorder {
+        game = game.with_recorder(recorder);
+    }
 
     for turn in game {
         match turn {
@@ -0,0 +1,123 @@
+use std::fmt;
+
+// Names the official engine uses for the two seats in its final summary.
+pub const SEAT_NAMES: [char; 2] = ['O', 'X'];
+
+// Keeps count as a game goes on. Seats are indexed 0 for player 1 and 1 for player 2. A player who fails to place a piece is stuck for the rest of the game, but the other keeps playing; the game is over when both are stuck.
+#[derive(Debug, Clone, Default)]
+pub struct Scoreboard {
+    scores: [usize; 2],
+    stuck_turns: [Option<usize>; 2],
+    turns: usize,
+}
+
+impl Scoreboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_stuck(&self, seat: usize) -> bool {
+        self.stuck_turns[seat].is_some()
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.is_stuck(0) && self.is_stuck(1)
+    }
+
+    pub fn record_placement(&mut self, seat: usize) {
+        assert!(!self.is_stuck(seat), "Stuck players can't place pieces");
+        self.turns += 1;
+        self.scores[seat] += 1;
+    }
+
+    pub fn record_stuck(&mut self, seat: usize) {
+        assert!(!self.is_stuck(seat), "Players only get stuck once");
+        self.turns += 1;
+        self.stuck_turns[seat] = Some(self.turns);
+    }
+
+    pub fn result(&self) -> GameResult {
+        GameResult {
+            scores: self.scores,
+            stuck_turns: self.stuck_turns,
+            turns: self.turns,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    // Pieces placed by each player.
+    pub scores: [usize; 2],
+    // The turn on which each player got stuck, counting both players' turns from 1.
+    pub stuck_turns: [Option<usize>; 2],
+    pub turns: usize,
+}
+
+impl GameResult {
+    // The winner's player ID, 1 or 2, or `None` for a draw. Being first to get stuck doesn't matter; only the number of pieces does.
+    pub fn winner(&self) -> Option<u8> {
+        match self.scores[0].cmp(&self.scores[1]) {
+            std::cmp::Ordering::Greater => Some(1),
+            std::cmp::Ordering::Less => Some(2),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+// The official engine's final lines: `== O fin: N` for player 1 and `== X fin: N` for player 2.
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "== {} fin: {}", SEAT_NAMES[0], self.scores[0])?;
+        write!(f, "== {} fin: {}", SEAT_NAMES[1], self.scores[1])
+    }
+}
+
+// Reads back one of those lines as a seat index and score.
+pub fn parse_summary_line(line: &str) -> Option<(usize, usize)> {
+    let rest = line.strip_prefix("== ")?;
+    let (name, score) = rest.split_once(" fin: ")?;
+    let seat = SEAT_NAMES.iter().position(|&c| name == c.to_string())?;
+    Some((seat, score.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoreboard_keeps_playing_after_first_player_is_stuck() {
+        let mut scoreboard = Scoreboard::new();
+        scoreboard.record_placement(0);
+        scoreboard.record_placement(1);
+        scoreboard.record_stuck(0);
+        assert!(!scoreboard.is_over());
+        scoreboard.record_placement(1);
+        scoreboard.record_placement(1);
+        scoreboard.record_stuck(1);
+        assert!(scoreboard.is_over());
+
+        let result = scoreboard.result();
+        assert_eq!(result.scores, [1, 3]);
+        assert_eq!(result.stuck_turns, [Some(3), Some(6)]);
+        assert_eq!(result.winner(), Some(2));
+        assert_eq!(result.to_string(), "== O fin: 1\n== X fin: 3");
+    }
+
+    #[test]
+    fn test_summary_round_trip() {
+        let result = GameResult {
+            scores: [12, 12],
+            stuck_turns: [Some(25), Some(26)],
+            turns: 26,
+        };
+        assert_eq!(result.winner(), None);
+        let seats: Vec<_> = result
+            .to_string()
+            .lines()
+            .filter_map(parse_summary_line)
+            .collect();
+        assert_eq!(seats, vec![(0, 12), (1, 12)]);
+        assert_eq!(parse_summary_line("== Z fin: 3"), None);
+    }
+}
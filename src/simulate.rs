@@ -0,0 +1,84 @@
+// Plays two strategies against each other directly, with no engine, processes or text in between.
+
+use crate::{
+    anfield::Anfield,
+    piece::generator::PieceGenerator,
+    rules,
+    scoring::{GameResult, Scoreboard},
+    strategy::Strategy,
+    symbols::Player,
+};
+
+// `map` is the starting board as seen by player 1, who is `strategy_a`, e.g. from `map::load(path, 1)`. Each strategy is shown the board from its own point of view, and the pieces are dealt from `seed` just as `filler-engine` would deal them.
+pub fn simulate(
+    strategy_a: &impl Strategy,
+    strategy_b: &impl Strategy,
+    map: &Anfield,
+    seed: u64,
+) -> GameResult {
+    let strategies: [&dyn Strategy; 2] = [strategy_a, strategy_b];
+    // Each strategy's view of the board, kept in step.
+    let mut views = [map.clone(), map.swapped()];
+    let mut pieces = PieceGenerator::for_map(map, seed);
+    let mut scoreboard = Scoreboard::new();
+
+    while !scoreboard.is_over() {
+        for seat in 0..2 {
+            if scoreboard.is_stuck(seat) {
+                continue;
+            }
+
+            let piece = pieces.next_piece();
+            let view = &views[seat];
+            let [x, y] = strategies[seat].choose_move(view, &piece);
+            if rules::check_placement(view, &piece, x, y).is_ok() {
+                views[seat].apply(&piece, x, y, Player::Own);
+                views[1 - seat].apply(&piece, x, y, Player::Opponent);
+                scoreboard.record_placement(seat);
+            } else {
+                scoreboard.record_stuck(seat);
+            }
+        }
+    }
+
+    scoreboard.result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        map::generator::{self, MapOptions},
+        piece::Piece,
+        strategy::attack::Attack,
+    };
+
+    // Always answers with a move that can't be legal.
+    struct Resign;
+
+    impl Strategy for Resign {
+        fn choose_move(&self, anfield: &Anfield, _piece: &Piece) -> [i32; 2] {
+            [anfield.width as i32, anfield.height as i32]
+        }
+    }
+
+    #[test]
+    fn test_simulate_is_deterministic() {
+        let map = generator::generate(&MapOptions::new(20, 15), 1, 3);
+        let a = simulate(&Attack::default(), &Attack::default(), &map, 8);
+        let b = simulate(&Attack::default(), &Attack::default(), &map, 8);
+        assert_eq!(a, b);
+        assert!(a.scores.iter().all(|&s| s > 0));
+        assert!(a.stuck_turns.iter().all(Option::is_some));
+    }
+
+    #[test]
+    fn test_simulate_lets_the_other_player_fill_the_board() {
+        let map = generator::generate(&MapOptions::new(10, 10), 1, 1);
+        let result = simulate(&Resign, &Attack::default(), &map, 2);
+        assert_eq!(result.scores[0], 0);
+        assert_eq!(result.stuck_turns[0], Some(1));
+        assert!(result.scores[1] > 5);
+        assert_eq!(result.winner(), Some(2));
+    }
+}
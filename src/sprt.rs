@@ -0,0 +1,146 @@
+// A sequential probability ratio test for deciding which of two players, A and B, is stronger. Games are played in pairs with the same seed and the seats swapped, so each pair scores A 0, 0.5, 1, 1.5 or 2. The log-likelihood ratio is the usual normal approximation for pentanomial pair results, as used by chess engine testers, with H0: A is `margin` Elo weaker than B, and H1: A is `margin` Elo stronger.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    AStronger,
+    BStronger,
+    Continue,
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Decision::AStronger => write!(f, "A is stronger"),
+            Decision::BStronger => write!(f, "B is stronger"),
+            Decision::Continue => write!(f, "inconclusive"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Sprt {
+    pub margin: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    // Number of pairs in which A scored 0, 0.5, 1, 1.5 and 2 points.
+    pub pairs: [usize; 5],
+}
+
+impl Sprt {
+    pub fn new(margin: f64, alpha: f64, beta: f64) -> Self {
+        assert!(margin > 0.0, "The Elo margin must be positive");
+        Sprt {
+            margin,
+            alpha,
+            beta,
+            pairs: [0; 5],
+        }
+    }
+
+    // `score` is A's total over the two games of a pair.
+    pub fn record_pair(&mut self, score: f64) {
+        let bin = (score * 2.0).round().clamp(0.0, 4.0) as usize;
+        self.pairs[bin] += 1;
+    }
+
+    pub fn pair_count(&self) -> usize {
+        self.pairs.iter().sum()
+    }
+
+    // [lower, upper]: accept H0 below the first, H1 above the second.
+    pub fn bounds(&self) -> [f64; 2] {
+        [
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        ]
+    }
+
+    pub fn llr(&self) -> f64 {
+        // Half a pair's worth of prior in each bin keeps the variance above zero, so a few lopsided pairs can't end the test on their own.
+        let counts = self.pairs.map(|n| n as f64 + 0.5);
+        let total: f64 = counts.iter().sum();
+        let score = |bin: usize| bin as f64 / 4.0;
+
+        let mean: f64 = (0..5).map(|i| counts[i] * score(i)).sum::<f64>() / total;
+        let variance: f64 = (0..5)
+            .map(|i| counts[i] * (score(i) - mean).powi(2))
+            .sum::<f64>()
+            / total;
+
+        let s0 = expected_score(-self.margin);
+        let s1 = expected_score(self.margin);
+        let pairs = self.pair_count() as f64;
+        pairs * (s1 - s0) * (2.0 * mean - s0 - s1) / (2.0 * variance)
+    }
+
+    pub fn decision(&self) -> Decision {
+        let llr = self.llr();
+        let [lower, upper] = self.bounds();
+        if llr >= upper {
+            Decision::AStronger
+        } else if llr <= lower {
+            Decision::BStronger
+        } else {
+            Decision::Continue
+        }
+    }
+}
+
+impl fmt::Display for Sprt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [lower, upper] = self.bounds();
+        write!(
+            f,
+            "pairs {} [{}], LLR {:.3} ({:.3}, {:.3}), ±{} Elo: {}",
+            self.pair_count(),
+            self.pairs.map(|n| n.to_string()).join(" "),
+            self.llr(),
+            lower,
+            upper,
+            self.margin,
+            self.decision()
+        )
+    }
+}
+
+// The expected score of a player `elo` points stronger than their opponent.
+pub fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_winner_is_found() {
+        let mut sprt = Sprt::new(20.0, 0.05, 0.05);
+        let mut pairs = 0;
+        while sprt.decision() == Decision::Continue {
+            // A wins three pairs in four outright and splits the fourth.
+            sprt.record_pair(if pairs % 4 == 3 { 1.0 } else { 2.0 });
+            pairs += 1;
+            assert!(pairs < 1000, "No decision: {}", sprt);
+        }
+        assert_eq!(sprt.decision(), Decision::AStronger);
+
+        let mut sprt = Sprt::new(20.0, 0.05, 0.05);
+        while sprt.decision() == Decision::Continue {
+            sprt.record_pair(0.5);
+        }
+        assert_eq!(sprt.decision(), Decision::BStronger);
+    }
+
+    #[test]
+    fn test_even_match_stays_open() {
+        let mut sprt = Sprt::new(10.0, 0.05, 0.05);
+        for i in 0..500 {
+            sprt.record_pair([0.0, 1.0, 2.0, 1.0][i % 4]);
+        }
+        assert_eq!(sprt.pair_count(), 500);
+        assert!(sprt.llr().abs() < 1e-9, "{}", sprt);
+        assert_eq!(sprt.decision(), Decision::Continue);
+    }
+}
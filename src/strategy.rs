@@ -1,7 +1,46 @@
 pub mod attack;
+pub mod expectimax;
+pub mod mcts;
+pub mod packing;
+pub mod phased;
+pub mod voronoi;
+pub mod wall;
+
+use std::any::Any;
 
 use crate::{anfield::Anfield, piece::Piece};
 
-pub trait Strategy {
+// `Any`, so that a strategy can be told apart from behind a `Box<dyn Strategy>`, as `Phased::set` does to keep phased strategies from nesting.
+pub trait Strategy: Any {
     fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2];
 }
+
+impl<S: Strategy + ?Sized> Strategy for Box<S> {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        (**self).choose_move(anfield, piece)
+    }
+}
+
+// Strategies that tools can pick by name on the command line.
+pub const NAMES: &[&str] = &[
+    "attack",
+    "expectimax",
+    "mcts",
+    "pack",
+    "phased",
+    "voronoi",
+    "wall",
+];
+
+pub fn by_name(name: &str) -> Option<Box<dyn Strategy>> {
+    match name {
+        "attack" => Some(Box::new(attack::Attack::default())),
+        "expectimax" => Some(Box::new(expectimax::Expectimax::default())),
+        "mcts" => Some(Box::new(mcts::Mcts::default())),
+        "pack" => Some(Box::new(packing::Pack)),
+        "phased" => Some(Box::new(phased::Phased::default())),
+        "voronoi" => Some(Box::new(voronoi::Voronoi)),
+        "wall" => Some(Box::new(wall::Wall)),
+        _ => None,
+    }
+}
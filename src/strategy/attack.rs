@@ -1,12 +1,27 @@
+pub mod params;
+
 use std::collections::VecDeque;
 
-use crate::{anfield::Anfield, cell::Cell, piece::Piece, strategy::Strategy, symbols::CellRole};
+use crate::{
+    anfield::Anfield, cell::Cell, piece::Piece, rules, strategy::Strategy, symbols::CellRole,
+};
+
+use params::Params;
 
-pub struct Attack;
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Attack {
+    pub params: Params,
+}
+
+impl Attack {
+    pub fn new(params: Params) -> Self {
+        Attack { params }
+    }
+}
 
 impl Strategy for Attack {
     fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
-        place(anfield, piece)
+        place(anfield, piece, &self.params)
     }
 }
 
@@ -14,11 +29,11 @@ impl Strategy for Attack {
 struct PossiblePlacement {
     pub x: usize,
     pub y: usize,
-    pub weight: usize,
+    pub weight: f64,
 }
 
-pub fn place(anfield: &Anfield, piece: &Piece) -> [i32; 2] {
-    let possible_placements = get_possible_placements(anfield, &piece);
+pub fn place(anfield: &Anfield, piece: &Piece, params: &Params) -> [i32; 2] {
+    let possible_placements = get_possible_placements(anfield, piece, params);
     let mut chosen_possible_placement = possible_placements[0];
     for possible_placement in possible_placements.iter().skip(1) {
         if possible_placement.weight > chosen_possible_placement.weight {
@@ -31,7 +46,11 @@ pub fn place(anfield: &Anfield, piece: &Piece) -> [i32; 2] {
 }
 
 // The "casting gymnastics" here (and in `try_fit`) are to allow pieces to be placed in such a way that their top-left corner is outside of the Anfield, as long as their shape cells are inside it. This can prevent the bot from getting stuck in situations where that's the only way to make a piece overlap its territory.
-fn get_possible_placements(anfield: &Anfield, piece: &Piece) -> Vec<PossiblePlacement> {
+fn get_possible_placements(
+    anfield: &Anfield,
+    piece: &Piece,
+    params: &Params,
+) -> Vec<PossiblePlacement> {
     let mut possible_placements = Vec::new();
 
     let x_min = -(piece.width as isize);
@@ -53,8 +72,7 @@ fn get_possible_placements(anfield: &Anfield, piece: &Piece) -> Vec<PossiblePlac
                     );
                     let s = s as usize;
                     let t = t as usize;
-                    let cell_distance = get_distance_to_opponent(anfield, s, t);
-                    possible_placement.weight += anfield.width + anfield.height - cell_distance;
+                    possible_placement.weight += cell_weight(anfield, s, t, params);
                 }
                 possible_placements.push(possible_placement);
             }
@@ -69,33 +87,36 @@ fn get_possible_placements(anfield: &Anfield, piece: &Piece) -> Vec<PossiblePlac
 }
 
 fn try_fit(anfield: &Anfield, piece: &Piece, x: isize, y: isize) -> Option<PossiblePlacement> {
-    let mut overlaps_with_own_territory = 0;
-    for cell in &piece.shape {
-        let s = x + cell.x as isize;
-        let t = y + cell.y as isize;
+    rules::check_placement(anfield, piece, x as i32, y as i32).ok()?;
 
-        if s >= anfield.width as isize || t >= anfield.height as isize || s < 0 || t < 0 {
-            return None;
-        }
+    Some(PossiblePlacement {
+        x: x as usize,
+        y: y as usize,
+        weight: 0.0,
+    })
+}
 
-        let cell_role = anfield.get_cell_role(s as usize, t as usize);
-        match cell_role {
-            Some(CellRole::OpponentSymbol | CellRole::OpponentLatestMove) => return None,
-            Some(CellRole::OwnSymbol | CellRole::OwnLatestMove) => overlaps_with_own_territory += 1,
-            Some(_) => (),
-            None => panic!("Cell ({}, {}) has no role", s, t),
-        }
+fn cell_weight(anfield: &Anfield, x: usize, y: usize, params: &Params) -> f64 {
+    let span = (anfield.width + anfield.height) as f64;
+    let mut weight = 0.0;
+
+    let cell_distance = get_distance_to_opponent(anfield, x, y);
+    if cell_distance != usize::MAX {
+        let distance = if cell_distance < params.border_width {
+            params.border_cost
+        } else {
+            cell_distance as f64
+        };
+        weight += params.distance_weight * (span - distance);
     }
 
-    if overlaps_with_own_territory != 1 {
-        return None;
+    if params.centre_bonus != 0.0 {
+        let width = anfield.width as f64;
+        let off_centre = (2.0 * x as f64 + 1.0 - width).abs() / width;
+        weight += params.centre_bonus * (1.0 - off_centre);
     }
 
-    Some(PossiblePlacement {
-        x: x as usize,
-        y: y as usize,
-        weight: 0,
-    })
+    weight
 }
 
 fn get_distance_to_opponent(anfield: &Anfield, x: usize, y: usize) -> usize {
@@ -139,3 +160,42 @@ fn neighbors(x: usize, y: usize, width: usize, height: usize) -> Vec<(usize, usi
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    // A 6x3 board with the opponent at the right-hand end of the middle row and us in the bottom-left corner.
+    fn make_anfield() -> Anfield {
+        map::parse("......\n.....$\n@.....", 1).expect("Valid map")
+    }
+
+    #[test]
+    fn test_default_weight_is_closeness() {
+        let anfield = make_anfield();
+        let params = Params::default();
+        assert_eq!(cell_weight(&anfield, 4, 1, &params), 8.0);
+        assert_eq!(cell_weight(&anfield, 0, 0, &params), 3.0);
+    }
+
+    #[test]
+    fn test_border_and_centre_line() {
+        let anfield = make_anfield();
+        let border = Params {
+            border_width: 2,
+            border_cost: 4.0,
+            ..Params::default()
+        };
+        assert_eq!(cell_weight(&anfield, 4, 1, &border), 5.0);
+        assert_eq!(cell_weight(&anfield, 3, 1, &border), 7.0);
+
+        let centre = Params {
+            distance_weight: 0.0,
+            centre_bonus: 3.0,
+            ..Params::default()
+        };
+        assert!((cell_weight(&anfield, 0, 0, &centre) - 0.5).abs() < 1e-9);
+        assert!((cell_weight(&anfield, 2, 0, &centre) - 2.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,147 @@
+// The knobs of Attack's evaluation, and a small `key = value` file format for them so that tuned values can be handed to the bot.
+
+use std::{fmt, fs, path::Path};
+
+use crate::errors::GameError;
+
+pub const KEYS: [&str; 4] = [
+    "distance_weight",
+    "border_width",
+    "border_cost",
+    "centre_bonus",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Params {
+    // How much a cell is worth for each step closer to the opponent than the far side of the board.
+    pub distance_weight: f64,
+    // Cells fewer than this many steps from the opponent are in the border...
+    pub border_width: usize,
+    // ... and are treated as if they were this many steps away, to leave the opponent to fill the gap. Jani Mäkelä's trick from the README is a width of 2 and a cost of 4.
+    pub border_cost: f64,
+    // Extra value for cells on the vertical line down the middle of the board, falling off linearly towards the sides (Robin Schramm's idea).
+    pub centre_bonus: f64,
+}
+
+// The values that reproduce the original evaluation, `width + height - distance` for each cell.
+impl Default for Params {
+    fn default() -> Self {
+        Params {
+            distance_weight: 1.0,
+            border_width: 0,
+            border_cost: 0.0,
+            centre_bonus: 0.0,
+        }
+    }
+}
+
+impl Params {
+    pub fn load(path: &Path) -> Result<Self, GameError> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    // Keys that are left out keep their default values. Blank lines and lines starting with `#` are ignored.
+    pub fn parse(text: &str) -> Result<Self, GameError> {
+        let mut params = Self::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            params
+                .set(line)
+                .ok_or_else(|| GameError::ParseParams(line.to_string()))?;
+        }
+        Ok(params)
+    }
+
+    // Sets one parameter from a `key = value` line.
+    pub fn set(&mut self, line: &str) -> Option<()> {
+        let (key, value) = line.split_once('=')?;
+        let value = value.trim();
+        match key.trim() {
+            "distance_weight" => self.distance_weight = value.parse().ok()?,
+            "border_width" => self.border_width = value.parse().ok()?,
+            "border_cost" => self.border_cost = value.parse().ok()?,
+            "centre_bonus" => self.centre_bonus = value.parse().ok()?,
+            _ => return None,
+        }
+        Some(())
+    }
+
+    // Each parameter can also be set by an environment variable named after its key, e.g. `FILLER_BORDER_WIDTH` for `border_width`.
+    pub fn env_var(key: &str) -> String {
+        format!("FILLER_{}", key.to_uppercase())
+    }
+
+    pub fn apply_env(&mut self) -> Result<(), GameError> {
+        for key in KEYS {
+            if let Ok(value) = std::env::var(Self::env_var(key)) {
+                let line = format!("{} = {}", key, value);
+                self.set(&line).ok_or(GameError::ParseParams(line))?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "distance_weight = {}", self.distance_weight)?;
+        writeln!(f, "border_width = {}", self.border_width)?;
+        writeln!(f, "border_cost = {}", self.border_cost)?;
+        writeln!(f, "centre_bonus = {}", self.centre_bonus)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let params = Params {
+            distance_weight: 1.25,
+            border_width: 2,
+            border_cost: 3.5,
+            centre_bonus: 0.75,
+        };
+        assert_eq!(Params::parse(&params.to_string()).unwrap(), params);
+    }
+
+    #[test]
+    fn test_missing_keys_keep_defaults() {
+        let params = Params::parse("# tuned\n\nborder_width = 1\n").unwrap();
+        assert_eq!(
+            params,
+            Params {
+                border_width: 1,
+                ..Params::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_every_key_can_be_set() {
+        let mut params = Params::default();
+        for key in KEYS {
+            assert!(params.set(&format!("{} = 3", key)).is_some(), "{}", key);
+        }
+        assert_eq!(params.to_string().lines().count(), KEYS.len());
+    }
+
+    #[test]
+    fn test_rejects_unknown_key_and_bad_value() {
+        assert!(matches!(
+            Params::parse("border_colour = red"),
+            Err(GameError::ParseParams(_))
+        ));
+        assert!(matches!(
+            Params::parse("border_width = -1"),
+            Err(GameError::ParseParams(_))
+        ));
+    }
+}
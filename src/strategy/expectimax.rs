@@ -0,0 +1,205 @@
+// Looks ahead past the current piece. The pieces to come are unknown, so they are sampled from the same distribution the engine deals from, and each of our candidate moves is valued by the average, over the sampled opponent pieces, of the position after the opponent's best reply. With `depth` above 1 the search goes on through our own next piece, and so on, alternating. Positions at the end are scored by territory margin, as in `voronoi`.
+//
+// Only the `breadth` moves that look best to `voronoi` are searched on our side, but the opponent is assumed to find its best reply among all its legal moves. The cost grows quickly with depth, and with the size of the board: on one the size of `map02`, even depth 1 took over a second a move by the 40th turn in my tests. So the search stops when its time budget runs out, and plays the best of the moves it finished valuing.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    anfield::Anfield,
+    piece::{Piece, generator::PieceGenerator},
+    rng::Rng,
+    rules,
+    strategy::{Strategy, voronoi},
+    symbols::{CellRole, Player},
+    territory,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Expectimax {
+    // Rounds of lookahead, each an opponent reply and, but for the last, our next move.
+    pub depth: usize,
+    // Pieces sampled for each unknown piece.
+    pub samples: usize,
+    // Our candidate moves searched at each turn.
+    pub breadth: usize,
+    // Thinking time per move.
+    pub budget: Duration,
+    pub seed: u64,
+}
+
+impl Default for Expectimax {
+    fn default() -> Self {
+        Expectimax {
+            depth: 1,
+            samples: 4,
+            breadth: 8,
+            budget: Duration::from_millis(500),
+            seed: 0,
+        }
+    }
+}
+
+impl Strategy for Expectimax {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        let deadline = Instant::now() + self.budget;
+        let candidates = self.candidates(anfield, piece);
+        let Some(&first) = candidates.first() else {
+            return [0, 0];
+        };
+
+        // The same sampled pieces are used in every branch at the same ply, so that moves are compared on the same futures. Mixing in how full the board is gives each turn different samples while keeping games reproducible.
+        let filled = anfield
+            .cells
+            .iter()
+            .filter(|&&c| c != CellRole::Empty)
+            .count();
+        let mut generator =
+            PieceGenerator::for_map(anfield, self.seed ^ Rng::new(filled as u64).next_u64());
+        let plies = 2 * self.depth.max(1) - 1;
+        let samples: Vec<Vec<Piece>> = (0..plies)
+            .map(|_| {
+                (0..self.samples.max(1))
+                    .map(|_| generator.next_piece())
+                    .collect()
+            })
+            .collect();
+
+        // A move whose search ran out of time isn't compared, and nor are the ones after it. If not even the first finished, it is played as Voronoi's pick.
+        let mut best = (f64::NEG_INFINITY, first);
+        for mv in candidates {
+            let after = play(anfield, piece, mv, Player::Own);
+            let Some(value) = self.opponent_to_move(&after, &samples, deadline) else {
+                break;
+            };
+            if value > best.0 {
+                best = (value, mv);
+            }
+        }
+        best.1
+    }
+}
+
+impl Expectimax {
+    // Our most promising moves, best first.
+    fn candidates(&self, anfield: &Anfield, piece: &Piece) -> Vec<[i32; 2]> {
+        let mut scored = voronoi::score_moves(anfield, piece);
+        scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+        scored
+            .into_iter()
+            .take(self.breadth.max(1))
+            .map(|(_, mv)| mv)
+            .collect()
+    }
+
+    // The average over the opponent's possible pieces of the position after its best reply. An opponent who can't place a piece passes. None once the deadline has passed.
+    fn opponent_to_move(
+        &self,
+        anfield: &Anfield,
+        samples: &[Vec<Piece>],
+        deadline: Instant,
+    ) -> Option<f64> {
+        let (pieces, rest) = samples.split_first().expect("A ply for the opponent");
+        let mut total = 0.0;
+        for piece in pieces {
+            let view = anfield.swapped();
+            let mut best = None;
+            for mv in rules::legal_moves(&view, piece) {
+                let after = play(anfield, piece, mv, Player::Opponent);
+                let value = self.own_to_move(&after, rest, deadline)?;
+                best = Some(best.map_or(value, |b: f64| b.min(value)));
+            }
+            total += match best {
+                Some(value) => value,
+                None => self.own_to_move(anfield, rest, deadline)?,
+            };
+        }
+        Some(total / pieces.len() as f64)
+    }
+
+    // The average over our possible pieces of the position after our best move, or just the position when the search is over.
+    fn own_to_move(
+        &self,
+        anfield: &Anfield,
+        samples: &[Vec<Piece>],
+        deadline: Instant,
+    ) -> Option<f64> {
+        if Instant::now() >= deadline {
+            return None;
+        }
+        let Some((pieces, rest)) = samples.split_first() else {
+            return Some(territory::territory(anfield).margin() as f64);
+        };
+        let mut total = 0.0;
+        for piece in pieces {
+            let mut best = None;
+            for mv in self.candidates(anfield, piece) {
+                let after = play(anfield, piece, mv, Player::Own);
+                let value = self.opponent_to_move(&after, rest, deadline)?;
+                best = Some(best.map_or(value, |b: f64| b.max(value)));
+            }
+            total += match best {
+                Some(value) => value,
+                None => self.opponent_to_move(anfield, rest, deadline)?,
+            };
+        }
+        Some(total / pieces.len() as f64)
+    }
+}
+
+// `anfield` is always from our point of view; the opponent's moves are in its own coordinates, which are the same.
+fn play(anfield: &Anfield, piece: &Piece, [x, y]: [i32; 2], player: Player) -> Anfield {
+    let mut after = anfield.clone();
+    after.apply(piece, x, y, player);
+    after
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{map::generator, simulate::simulate, strategy::voronoi::Voronoi};
+
+    // With time to spare, so that games don't depend on the machine.
+    fn unhurried() -> Expectimax {
+        Expectimax {
+            budget: Duration::from_secs(60),
+            ..Expectimax::default()
+        }
+    }
+
+    #[test]
+    fn test_plays_legal_moves_reproducibly() {
+        let map = generator::generate(&generator::MapOptions::new(16, 12), 1, 6);
+        let a = simulate(&unhurried(), &Voronoi, &map, 3);
+        let b = simulate(&unhurried(), &Voronoi, &map, 3);
+        assert_eq!(a, b);
+        assert!(a.scores[0] > 0);
+    }
+
+    #[test]
+    fn test_deeper_search_still_moves() {
+        let map = generator::generate(&generator::MapOptions::new(10, 8), 1, 2);
+        let deep = Expectimax {
+            depth: 2,
+            samples: 2,
+            breadth: 3,
+            seed: 5,
+            ..unhurried()
+        };
+        let mut pieces = PieceGenerator::for_map(&map, 1);
+        let piece = pieces.next_piece();
+        let [x, y] = deep.choose_move(&map, &piece);
+        assert_eq!(rules::check_placement(&map, &piece, x, y), Ok(()));
+    }
+
+    #[test]
+    fn test_out_of_time_plays_voronois_pick() {
+        let map = generator::generate(&generator::MapOptions::new(16, 12), 1, 6);
+        let piece = PieceGenerator::for_map(&map, 2).next_piece();
+        let hurried = Expectimax {
+            budget: Duration::ZERO,
+            ..Expectimax::default()
+        };
+        let first = hurried.candidates(&map, &piece)[0];
+        assert_eq!(hurried.choose_move(&map, &piece), first);
+    }
+}
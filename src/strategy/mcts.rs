@@ -0,0 +1,280 @@
+// Monte Carlo tree search. The tree has two levels: our legal placements, and below each of them the opponent's replies to each of a few sampled pieces it might get next. Each iteration walks down the tree by UCB1, picking the opponent's piece at random, then plays a quick random playout from there and scores it by who has more territory at the end. When the time budget runs out, the most visited of our moves is played.
+//
+// Playouts are what a greedy evaluation misses: a move that walls us in looks fine to Attack, but random continuations from it keep running out of room.
+
+use std::time::{Duration, Instant};
+
+use crate::{
+    anfield::Anfield,
+    piece::{Piece, generator::PieceGenerator},
+    rng::Rng,
+    rules,
+    strategy::Strategy,
+    symbols::{CellRole, Player},
+    territory,
+};
+
+// Random placements tried before falling back to a search of every legal move.
+const ATTEMPTS: usize = 16;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Mcts {
+    // Thinking time per move.
+    pub budget: Duration,
+    // Stop after this many iterations, even with time to spare. Makes games reproducible.
+    pub iterations: Option<usize>,
+    // Opponent pieces sampled at the root.
+    pub samples: usize,
+    // Rounds of random play after the tree, before scoring.
+    pub playout_rounds: usize,
+    pub exploration: f64,
+    pub seed: u64,
+}
+
+impl Default for Mcts {
+    fn default() -> Self {
+        Mcts {
+            budget: Duration::from_millis(500),
+            iterations: None,
+            samples: 4,
+            playout_rounds: 8,
+            exploration: std::f64::consts::SQRT_2,
+            seed: 0,
+        }
+    }
+}
+
+// Visits and total value, from our point of view, of a move.
+#[derive(Debug, Clone, Copy, Default)]
+struct Stats {
+    visits: u32,
+    value: f64,
+}
+
+impl Stats {
+    fn record(&mut self, value: f64) {
+        self.visits += 1;
+        self.value += value;
+    }
+
+    // UCB1. `maximise` is false for the opponent's moves, which it picks to keep our value low.
+    fn ucb(&self, parent_visits: u32, exploration: f64, maximise: bool) -> f64 {
+        if self.visits == 0 {
+            return f64::INFINITY;
+        }
+        let mean = self.value / self.visits as f64;
+        let mean = if maximise { mean } else { 1.0 - mean };
+        mean + exploration * ((parent_visits.max(1) as f64).ln() / self.visits as f64).sqrt()
+    }
+}
+
+type Replies = Vec<([i32; 2], Stats)>;
+
+struct OwnNode {
+    mv: [i32; 2],
+    stats: Stats,
+    // For each sampled opponent piece, its legal replies, once first needed.
+    replies: Vec<Option<Replies>>,
+}
+
+impl Strategy for Mcts {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        let start = Instant::now();
+        let filled = anfield
+            .cells
+            .iter()
+            .filter(|&&c| c != CellRole::Empty)
+            .count();
+        let mut rng = Rng::new(self.seed ^ Rng::new(filled as u64).next_u64());
+        let mut generator = PieceGenerator::for_map(anfield, rng.next_u64());
+        let pieces: Vec<Piece> = (0..self.samples.max(1))
+            .map(|_| generator.next_piece())
+            .collect();
+
+        let mut tree: Vec<OwnNode> = rules::legal_moves(anfield, piece)
+            .into_iter()
+            .map(|mv| OwnNode {
+                mv,
+                stats: Stats::default(),
+                replies: vec![None; pieces.len()],
+            })
+            .collect();
+        if tree.len() <= 1 {
+            return tree.first().map_or([0, 0], |node| node.mv);
+        }
+
+        let mut iterations = 0;
+        while start.elapsed() < self.budget && self.iterations.is_none_or(|n| iterations < n) {
+            iterations += 1;
+            let total: u32 = tree.iter().map(|node| node.stats.visits).sum();
+            let node = select(&mut tree, |node| &node.stats, total, self.exploration, true);
+
+            let mut board = Board::new(anfield);
+            board.play(piece, node.mv, 0);
+
+            let k = rng.below(pieces.len());
+            let replies = node.replies[k].get_or_insert_with(|| {
+                rules::legal_moves(&board.views[1], &pieces[k])
+                    .into_iter()
+                    .map(|mv| (mv, Stats::default()))
+                    .collect()
+            });
+            let value = if replies.is_empty() {
+                self.playout(board, [false, true], &mut rng)
+            } else {
+                let total = replies.iter().map(|(_, stats)| stats.visits).sum();
+                let reply = select(replies, |(_, stats)| stats, total, self.exploration, false);
+                board.play(&pieces[k], reply.0, 1);
+                let value = self.playout(board, [false, false], &mut rng);
+                reply.1.record(value);
+                value
+            };
+            node.stats.record(value);
+        }
+
+        tree.iter()
+            .max_by_key(|node| node.stats.visits)
+            .map_or([0, 0], |node| node.mv)
+    }
+}
+
+impl Mcts {
+    // Random moves for both players, us first, then 1 for a win on territory, 0 for a loss and a half for a tie.
+    fn playout(&self, mut board: Board, mut stuck: [bool; 2], rng: &mut Rng) -> f64 {
+        let mut pieces = PieceGenerator::for_map(&board.views[0], rng.next_u64());
+        for _ in 0..self.playout_rounds {
+            for (seat, is_stuck) in stuck.iter_mut().enumerate() {
+                if *is_stuck {
+                    continue;
+                }
+                let piece = pieces.next_piece();
+                match random_move(&board.views[seat], &piece, rng) {
+                    Some(mv) => board.play(&piece, mv, seat),
+                    None => *is_stuck = true,
+                }
+            }
+            if stuck == [true, true] {
+                break;
+            }
+        }
+
+        match territory::territory(&board.views[0]).margin() {
+            m if m > 0 => 1.0,
+            m if m < 0 => 0.0,
+            _ => 0.5,
+        }
+    }
+}
+
+// The child with the highest UCB1 score; the first such, so that untried moves are tried in order.
+fn select<T>(
+    children: &mut [T],
+    stats: impl Fn(&T) -> &Stats,
+    parent_visits: u32,
+    exploration: f64,
+    maximise: bool,
+) -> &mut T {
+    let mut best = 0;
+    let mut best_score = f64::NEG_INFINITY;
+    for (i, child) in children.iter().enumerate() {
+        let score = stats(child).ucb(parent_visits, exploration, maximise);
+        if score > best_score {
+            best = i;
+            best_score = score;
+        }
+    }
+    &mut children[best]
+}
+
+// The board from both sides, kept in step, so that neither player's moves need the board swapping first.
+struct Board {
+    views: [Anfield; 2],
+}
+
+impl Board {
+    fn new(anfield: &Anfield) -> Self {
+        Board {
+            views: [anfield.clone(), anfield.swapped()],
+        }
+    }
+
+    fn play(&mut self, piece: &Piece, [x, y]: [i32; 2], seat: usize) {
+        self.views[seat].apply(piece, x, y, Player::Own);
+        self.views[1 - seat].apply(piece, x, y, Player::Opponent);
+    }
+}
+
+// A random legal move, found quickly by lining a random cell of the piece up with a random cell on the edge of our territory. Only when that keeps failing are all the legal moves listed.
+fn random_move(anfield: &Anfield, piece: &Piece, rng: &mut Rng) -> Option<[i32; 2]> {
+    let frontier: Vec<usize> = (0..anfield.cells.len())
+        .filter(|&i| {
+            territory::belongs_to(anfield, i, Player::Own)
+                && territory::neighbors(anfield, i).any(|n| anfield.cells[n] == CellRole::Empty)
+        })
+        .collect();
+    if frontier.is_empty() {
+        return None;
+    }
+
+    for _ in 0..ATTEMPTS {
+        let index = frontier[rng.below(frontier.len())];
+        let cell = piece.shape[rng.below(piece.shape.len())];
+        let x = (index % anfield.width) as i32 - cell.x as i32;
+        let y = (index / anfield.width) as i32 - cell.y as i32;
+        if rules::check_placement(anfield, piece, x, y).is_ok() {
+            return Some([x, y]);
+        }
+    }
+
+    let moves = rules::legal_moves(anfield, piece);
+    (!moves.is_empty()).then(|| moves[rng.below(moves.len())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, map, map::generator, simulate::simulate, strategy::voronoi::Voronoi};
+
+    fn fixed(iterations: usize) -> Mcts {
+        Mcts {
+            budget: Duration::from_secs(60),
+            iterations: Some(iterations),
+            ..Mcts::default()
+        }
+    }
+
+    #[test]
+    fn test_random_move_is_legal() {
+        let anfield = map::parse("......\n..@...\n....$.", 1).expect("Valid map");
+        let piece = PieceGenerator::new(1, 3).next_piece();
+        let mut rng = Rng::new(4);
+        for _ in 0..50 {
+            let [x, y] = random_move(&anfield, &piece, &mut rng).expect("A legal move");
+            assert_eq!(rules::check_placement(&anfield, &piece, x, y), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_plays_the_only_move_without_searching() {
+        let anfield = map::parse("@.\n$$", 1).expect("Valid map");
+        let piece = Piece {
+            width: 2,
+            height: 1,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }],
+        };
+        let search = Mcts {
+            budget: Duration::ZERO,
+            ..Mcts::default()
+        };
+        assert_eq!(search.choose_move(&anfield, &piece), [0, 0]);
+    }
+
+    #[test]
+    fn test_plays_a_reproducible_game() {
+        let map = generator::generate(&generator::MapOptions::new(14, 10), 1, 3);
+        let a = simulate(&fixed(40), &Voronoi, &map, 8);
+        let b = simulate(&fixed(40), &Voronoi, &map, 8);
+        assert_eq!(a, b);
+        assert!(a.scores[0] > 0);
+    }
+}
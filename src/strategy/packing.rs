@@ -0,0 +1,141 @@
+// Packs our own space, for once the players are separated (see `territory::separated`), which the phased strategy can hand over to it. The distance to the opponent no longer says anything then, and all that's left is to fit as many pieces as possible into our own space. So placements are ranked by how few one-cell holes they leave, since most pieces can't fill those, and then by how much they touch filled cells and the edges of the board, which fills the space from the edges inwards and keeps the rest of it in one piece.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    anfield::Anfield,
+    piece::Piece,
+    rules,
+    strategy::Strategy,
+    symbols::{CellRole, Player},
+    territory,
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pack;
+
+impl Strategy for Pack {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        pack(anfield, piece).unwrap_or([0, 0])
+    }
+}
+
+// Plays as the strategy it wraps until the players are separated, then packs. This is how maximilian finishes a game by default, since Attack has nothing to aim at once the opponent is walled off.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PackWhenSeparated<S>(pub S);
+
+impl<S: Strategy> Strategy for PackWhenSeparated<S> {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        if territory::separated(anfield)
+            && let Some(mv) = pack(anfield, piece)
+        {
+            return mv;
+        }
+        self.0.choose_move(anfield, piece)
+    }
+}
+
+// The best placement by packing, or `None` if there's no legal one.
+pub fn pack(anfield: &Anfield, piece: &Piece) -> Option<[i32; 2]> {
+    // One scratch copy of the board, each move filled in and cleared again after scoring it.
+    let mut after = anfield.clone();
+    let mut best: Option<((isize, usize), [i32; 2])> = None;
+    for mv in rules::legal_moves(anfield, piece) {
+        let filled = after.fill(piece, mv[0], mv[1], Player::Own);
+        let score = score(anfield, &after, &filled);
+        after.clear(&filled);
+        if best.is_none_or(|(best_score, _)| score > best_score) {
+            best = Some((score, mv));
+        }
+    }
+    best.map(|(_, mv)| mv)
+}
+
+// New one-cell holes (negated, so that higher is better), then the sides of the newly filled cells that touch filled cells or the edge of the board. `after` is the board once the cells `placed` have been filled.
+fn score(anfield: &Anfield, after: &Anfield, placed: &[usize]) -> (isize, usize) {
+    let empty = |anfield: &Anfield, index: usize| anfield.cells[index] == CellRole::Empty;
+    let is_hole = |anfield: &Anfield, index: usize| {
+        empty(anfield, index) && !territory::neighbors(anfield, index).any(|n| empty(anfield, n))
+    };
+
+    let holes: BTreeSet<usize> = placed
+        .iter()
+        .flat_map(|&index| territory::neighbors(after, index))
+        .filter(|&n| is_hole(after, n) && !is_hole(anfield, n))
+        .collect();
+    let contact = placed
+        .iter()
+        .map(|&index| {
+            4 - territory::neighbors(after, index)
+                .filter(|&n| empty(after, n))
+                .count()
+        })
+        .sum();
+
+    (-(holes.len() as isize), contact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, map};
+
+    // The opponent is walled off on the right, and we have the top row and one cell below it to fill.
+    fn make_anfield() -> Anfield {
+        map::parse("....@$\n.@@@@$", 1).expect("Valid map")
+    }
+
+    fn make_domino() -> Piece {
+        Piece {
+            width: 1,
+            height: 2,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 0, y: 1 }],
+        }
+    }
+
+    fn score_move(anfield: &Anfield, piece: &Piece, [x, y]: [i32; 2]) -> (isize, usize) {
+        let mut after = anfield.clone();
+        let filled = after.fill(piece, x, y, Player::Own);
+        score(anfield, &after, &filled)
+    }
+
+    #[test]
+    fn test_score_counts_holes_and_contact() {
+        let anfield = make_anfield();
+        // Filling (2, 0) leaves (3, 0) shut in on every side.
+        assert_eq!(score_move(&anfield, &make_domino(), [2, 0]), (-1, 2));
+        assert_eq!(score_move(&anfield, &make_domino(), [3, 0]), (0, 3));
+        assert_eq!(score_move(&anfield, &make_domino(), [1, 0]), (0, 2));
+    }
+
+    #[test]
+    fn test_fills_the_corner_first() {
+        assert_eq!(pack(&make_anfield(), &make_domino()), Some([3, 0]));
+        assert_eq!(Pack.choose_move(&make_anfield(), &make_domino()), [3, 0]);
+    }
+
+    // Always answers the same, to show when it was asked.
+    struct Fixed;
+
+    impl Strategy for Fixed {
+        fn choose_move(&self, _anfield: &Anfield, _piece: &Piece) -> [i32; 2] {
+            [7, 7]
+        }
+    }
+
+    #[test]
+    fn test_packs_only_once_separated() {
+        let strategy = PackWhenSeparated(Fixed);
+        assert_eq!(
+            strategy.choose_move(&make_anfield(), &make_domino()),
+            [3, 0]
+        );
+        let open = map::parse(
+            "....@.
+.@@@@$",
+            1,
+        )
+        .expect("Valid map");
+        assert_eq!(strategy.choose_move(&open, &make_domino()), [7, 7]);
+    }
+}
@@ -0,0 +1,227 @@
+// Hands each turn to a different strategy depending on the state of the game, worked out afresh from the board every turn: the opening, while the two territories are still some way apart; contact, once they're close; separated, once no empty cell is left that both players can reach; and the opponent stuck, once it has no empty cell next to its territory to place a piece on. The last two are about packing our own space, the first two about taking it.
+
+use std::any::Any;
+
+use crate::{
+    anfield::Anfield,
+    errors::GameError,
+    piece::Piece,
+    strategy::{self, Strategy},
+    symbols::{CellRole, Player},
+    territory::{self, UNREACHABLE},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Opening,
+    Contact,
+    Separated,
+    OpponentStuck,
+}
+
+pub const PHASES: [Phase; 4] = [
+    Phase::Opening,
+    Phase::Contact,
+    Phase::Separated,
+    Phase::OpponentStuck,
+];
+
+impl Phase {
+    pub fn name(self) -> &'static str {
+        match self {
+            Phase::Opening => "opening",
+            Phase::Contact => "contact",
+            Phase::Separated => "separated",
+            Phase::OpponentStuck => "opponent_stuck",
+        }
+    }
+}
+
+// The territories are in contact once they're this many steps apart or fewer, through empty cells.
+pub const CONTACT_DISTANCE: usize = 4;
+
+pub fn classify(anfield: &Anfield, contact_distance: usize) -> Phase {
+    let own = territory::distances(anfield, Player::Own);
+    let opponent = territory::distances(anfield, Player::Opponent);
+    if territory::reach(&own, &opponent, anfield)[1] == 0 {
+        return Phase::OpponentStuck;
+    }
+
+    // The fewest steps from one territory to the other, through empty cells.
+    let gap = own
+        .iter()
+        .zip(&opponent)
+        .zip(&anfield.cells)
+        .filter(|&((&own, &opponent), &role)| {
+            role == CellRole::Empty && own != UNREACHABLE && opponent != UNREACHABLE
+        })
+        .map(|((own, opponent), _)| own + opponent)
+        .min();
+    match gap {
+        None => Phase::Separated,
+        Some(gap) if gap > contact_distance => Phase::Opening,
+        Some(_) => Phase::Contact,
+    }
+}
+
+// The strategy for each phase in the order of `PHASES`, by name (see `strategy::by_name`): Attack to close in, Voronoi once the fight is on, and packing once the players are apart (see `packing`).
+pub const DEFAULTS: [&str; 4] = ["attack", "voronoi", "pack", "pack"];
+
+pub struct Phased {
+    pub opening: Box<dyn Strategy>,
+    pub contact: Box<dyn Strategy>,
+    pub separated: Box<dyn Strategy>,
+    pub opponent_stuck: Box<dyn Strategy>,
+    pub contact_distance: usize,
+}
+
+impl Default for Phased {
+    fn default() -> Self {
+        let by_name = |name| strategy::by_name(name).expect("A known strategy");
+        Phased {
+            opening: by_name(DEFAULTS[0]),
+            contact: by_name(DEFAULTS[1]),
+            separated: by_name(DEFAULTS[2]),
+            opponent_stuck: by_name(DEFAULTS[3]),
+            contact_distance: CONTACT_DISTANCE,
+        }
+    }
+}
+
+impl Phased {
+    pub fn strategy(&self, phase: Phase) -> &dyn Strategy {
+        match phase {
+            Phase::Opening => &self.opening,
+            Phase::Contact => &self.contact,
+            Phase::Separated => &self.separated,
+            Phase::OpponentStuck => &self.opponent_stuck,
+        }
+    }
+
+    // A phased strategy can't be given another for one of its phases.
+    pub fn set(&mut self, phase: Phase, strategy: Box<dyn Strategy>) -> Result<(), GameError> {
+        if (strategy.as_ref() as &dyn Any).is::<Phased>() {
+            return Err(GameError::NestedPhased(phase.name()));
+        }
+        match phase {
+            Phase::Opening => self.opening = strategy,
+            Phase::Contact => self.contact = strategy,
+            Phase::Separated => self.separated = strategy,
+            Phase::OpponentStuck => self.opponent_stuck = strategy,
+        }
+        Ok(())
+    }
+}
+
+impl Strategy for Phased {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        let phase = classify(anfield, self.contact_distance);
+        self.strategy(phase).choose_move(anfield, piece)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        map,
+        map::generator,
+        simulate::simulate,
+        strategy::{attack::Attack, voronoi::Voronoi},
+    };
+    use std::{cell::RefCell, rc::Rc};
+
+    // Answers with the number of its phase, to show who was asked.
+    struct Marker(i32);
+
+    impl Strategy for Marker {
+        fn choose_move(&self, _anfield: &Anfield, _piece: &Piece) -> [i32; 2] {
+            [self.0, self.0]
+        }
+    }
+
+    fn classify_map(text: &str) -> Phase {
+        classify(&map::parse(text, 1).expect("Valid map"), CONTACT_DISTANCE)
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(classify_map("@.........$"), Phase::Opening);
+        assert_eq!(classify_map("@...$....."), Phase::Contact);
+        assert_eq!(classify_map("@.$"), Phase::Contact);
+        assert_eq!(classify_map("..@$..\n..@$.."), Phase::Separated);
+        assert_eq!(classify_map("...@$\n...@@"), Phase::OpponentStuck);
+    }
+
+    #[test]
+    fn test_delegates_by_phase() {
+        let mut phased = Phased::default();
+        for (i, phase) in PHASES.into_iter().enumerate() {
+            phased
+                .set(phase, Box::new(Marker(i as i32)))
+                .expect("Not phased");
+        }
+        let piece = Piece {
+            width: 1,
+            height: 1,
+            shape: Vec::new(),
+        };
+        for (i, text) in ["@.........$", "@...$", "@$..\n@$..", "..@$\n..@@"]
+            .into_iter()
+            .enumerate()
+        {
+            let anfield = map::parse(text, 1).expect("Valid map");
+            assert_eq!(
+                phased.choose_move(&anfield, &piece),
+                [i as i32; 2],
+                "{}",
+                text
+            );
+        }
+    }
+
+    // Notes its phase each time it's asked, then plays as that phase's default strategy would.
+    struct Logged {
+        phase: Phase,
+        log: Rc<RefCell<Vec<Phase>>>,
+        inner: Box<dyn Strategy>,
+    }
+
+    impl Strategy for Logged {
+        fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+            self.log.borrow_mut().push(self.phase);
+            self.inner.choose_move(anfield, piece)
+        }
+    }
+
+    #[test]
+    fn test_switches_phase_during_a_game() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut phased = Phased::default();
+        for (phase, name) in PHASES.into_iter().zip(DEFAULTS) {
+            let inner = strategy::by_name(name).expect("A known strategy");
+            let log = Rc::clone(&log);
+            phased
+                .set(phase, Box::new(Logged { phase, log, inner }))
+                .expect("Not phased");
+        }
+
+        let map = generator::generate(&generator::MapOptions::new(20, 15), 1, 4);
+        simulate(&phased, &Attack::default(), &map, 9);
+        // Each phase in turn, as the players close in, are sealed off from each other and one gets stuck.
+        let mut phases = log.borrow().clone();
+        phases.dedup();
+        assert_eq!(phases, PHASES);
+    }
+
+    #[test]
+    fn test_rejects_a_nested_phased_strategy() {
+        let mut phased = Phased::default();
+        let nested = strategy::by_name("phased").expect("A known strategy");
+        assert!(matches!(
+            phased.set(Phase::Contact, nested),
+            Err(GameError::NestedPhased("contact"))
+        ));
+        assert!(phased.set(Phase::Contact, Box::new(Voronoi)).is_ok());
+    }
+}
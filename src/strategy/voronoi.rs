@@ -0,0 +1,82 @@
+// Plays for territory rather than contact: tries every legal placement and keeps the one after which we reach the most empty cells before the opponent does, less those the opponent reaches first (see `territory`). Ties, common while the players are still far apart, go to the placement whose cells are closest to the opponent, so that the bot still advances.
+
+use crate::{
+    anfield::Anfield, piece::Piece, rules, strategy::Strategy, symbols::Player, territory,
+};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Voronoi;
+
+impl Strategy for Voronoi {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        let mut best: Option<(Score, [i32; 2])> = None;
+        for (score, mv) in score_moves(anfield, piece) {
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, mv));
+            }
+        }
+        best.map_or([0, 0], |(_, mv)| mv)
+    }
+}
+
+// Territory margin after the move, then how close its cells are to the opponent (negated, so that higher is better).
+pub type Score = (isize, isize);
+
+// Every legal move with its score, in the order of `rules::legal_moves`.
+pub fn score_moves(anfield: &Anfield, piece: &Piece) -> Vec<(Score, [i32; 2])> {
+    let to_opponent = territory::distances(anfield, Player::Opponent);
+
+    // One scratch copy of the board, each move filled in and cleared again after scoring it.
+    let mut after = anfield.clone();
+    rules::legal_moves(anfield, piece)
+        .into_iter()
+        .map(|[x, y]| {
+            let filled = after.fill(piece, x, y, Player::Own);
+            let margin = territory::territory(&after).margin();
+            after.clear(&filled);
+
+            let approach = territory::approach(anfield, piece, [x, y], &to_opponent);
+            ((margin, -(approach as isize)), [x, y])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, map, map::generator, simulate::simulate, strategy::attack::Attack};
+
+    fn make_bar() -> Piece {
+        Piece {
+            width: 1,
+            height: 3,
+            shape: vec![
+                Cell { x: 0, y: 0 },
+                Cell { x: 0, y: 1 },
+                Cell { x: 0, y: 2 },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_walls_off_the_board() {
+        // Dropping the bar down column 2 seals the whole right-hand side away from the opponent.
+        let anfield = map::parse("......\n..@...\n$.....", 1).expect("Valid map");
+        let [x, y] = Voronoi.choose_move(&anfield, &make_bar());
+        assert_eq!(rules::check_placement(&anfield, &make_bar(), x, y), Ok(()));
+
+        let mut after = anfield.clone();
+        after.apply(&make_bar(), x, y, Player::Own);
+        let split = territory::territory(&after);
+        assert!(split.own > split.opponent, "{:?} after {} {}", split, x, y);
+    }
+
+    #[test]
+    fn test_plays_a_whole_game() {
+        let map = generator::generate(&generator::MapOptions::new(20, 15), 1, 4);
+        let a = simulate(&Voronoi, &Attack::default(), &map, 9);
+        let b = simulate(&Voronoi, &Attack::default(), &map, 9);
+        assert_eq!(a, b);
+        assert!(a.scores[0] > 0);
+    }
+}
@@ -0,0 +1,127 @@
+// Builds walls to seal off the opponent, the idea of taking half the board and outlasting the other side. It looks for the narrowest points of the empty space that both players can reach, the articulation points (see `territory`), and keeps those where a cell of ours would leave us more room than the opponent. Placements are then ranked by the Voronoi margin plus how much more of the board we can reach than the opponent afterwards, so that closing a corridor on the right side counts twice over; then by how close the piece comes to one of those chokepoints, so that the bot heads for the next one; and last by the Voronoi score.
+
+use crate::{
+    anfield::Anfield,
+    piece::Piece,
+    rules,
+    strategy::Strategy,
+    symbols::{CellRole, Player},
+    territory::{self, UNREACHABLE},
+};
+
+// Only the chokepoints nearest to us are tested, since each test takes two searches of the board.
+const MAX_CHOKEPOINTS: usize = 16;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Wall;
+
+impl Strategy for Wall {
+    fn choose_move(&self, anfield: &Anfield, piece: &Piece) -> [i32; 2] {
+        let own = territory::distances(anfield, Player::Own);
+        let opponent = territory::distances(anfield, Player::Opponent);
+        let targets = chokepoints(anfield, &own, &opponent);
+        let to_target = territory::distances_from(anfield, &targets);
+
+        // One scratch copy of the board, each move filled in and cleared again after scoring it.
+        let mut after = anfield.clone();
+        let mut best = None;
+        for mv in rules::legal_moves(anfield, piece) {
+            let filled = after.fill(piece, mv[0], mv[1], Player::Own);
+            let after_own = territory::distances(&after, Player::Own);
+            let after_opponent = territory::distances(&after, Player::Opponent);
+
+            let [own_reach, opponent_reach] = territory::reach(&after_own, &after_opponent, &after);
+            let near = if targets.is_empty() {
+                0
+            } else {
+                territory::approach(anfield, piece, mv, &to_target)
+            };
+            let margin = territory::territory_from(&after, &after_own, &after_opponent).margin();
+            after.clear(&filled);
+            let approach = territory::approach(anfield, piece, mv, &opponent);
+
+            let score = (
+                own_reach as isize - opponent_reach as isize + margin,
+                -(near as isize),
+                margin,
+                -(approach as isize),
+            );
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, mv));
+            }
+        }
+
+        best.map_or([0, 0], |(_, mv)| mv)
+    }
+}
+
+// Articulation points that both players can reach and where a cell of ours would improve how much more of the board we can reach than the opponent, nearest to us first. `own` and `opponent` are the players' distances.
+pub fn chokepoints(anfield: &Anfield, own: &[usize], opponent: &[usize]) -> Vec<usize> {
+    let margin = |own: &[usize], opponent: &[usize], anfield: &Anfield| {
+        let [a, b] = territory::reach(own, opponent, anfield);
+        a as isize - b as isize
+    };
+    let before = margin(own, opponent, anfield);
+
+    let mut candidates: Vec<usize> = territory::articulation_points(anfield)
+        .into_iter()
+        .filter(|&i| own[i] != UNREACHABLE && opponent[i] != UNREACHABLE)
+        .collect();
+    candidates.sort_by_key(|&i| own[i]);
+    candidates.truncate(MAX_CHOKEPOINTS);
+
+    let mut after = anfield.clone();
+    candidates
+        .into_iter()
+        .filter(|&i| {
+            after.cells[i] = CellRole::OwnSymbol;
+            let own = territory::distances(&after, Player::Own);
+            let opponent = territory::distances(&after, Player::Opponent);
+            let improves = margin(&own, &opponent, &after) > before;
+            after.cells[i] = CellRole::Empty;
+            improves
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cell::Cell, map, map::generator, simulate::simulate, strategy::voronoi::Voronoi};
+
+    // Our wall down column 5 has one gap left, at (5, 2). The opponent is in the smaller room on the right.
+    fn make_anfield() -> Anfield {
+        map::parse("@....@....\n.....@.$..\n..........", 1).expect("Valid map")
+    }
+
+    fn make_bar() -> Piece {
+        Piece {
+            width: 1,
+            height: 2,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 0, y: 1 }],
+        }
+    }
+
+    #[test]
+    fn test_finds_the_gap() {
+        let anfield = make_anfield();
+        let own = territory::distances(&anfield, Player::Own);
+        let opponent = territory::distances(&anfield, Player::Opponent);
+        // The gap itself first, then the cells either side of it, which would close it just as well.
+        assert_eq!(chokepoints(&anfield, &own, &opponent), vec![25, 24, 26]);
+    }
+
+    #[test]
+    fn test_closes_the_gap() {
+        assert_eq!(Wall.choose_move(&make_anfield(), &make_bar()), [5, 1]);
+    }
+
+    #[test]
+    fn test_plays_a_whole_game() {
+        let map = generator::generate(&generator::MapOptions::new(20, 15), 1, 4);
+        let a = simulate(&Wall, &Voronoi, &map, 9);
+        let b = simulate(&Wall, &Voronoi, &map, 9);
+        assert_eq!(a, b);
+        assert!(a.scores[0] > 0);
+    }
+}
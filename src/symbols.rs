@@ -16,6 +16,49 @@ pub enum CellRole {
     OpponentLatestMove,
 }
 
+impl CellRole {
+    // The same cell as seen by the other player.
+    pub fn swapped(self) -> Self {
+        match self {
+            CellRole::Empty => CellRole::Empty,
+            CellRole::OwnSymbol => CellRole::OpponentSymbol,
+            CellRole::OpponentSymbol => CellRole::OwnSymbol,
+            CellRole::OwnLatestMove => CellRole::OpponentLatestMove,
+            CellRole::OpponentLatestMove => CellRole::OwnLatestMove,
+        }
+    }
+}
+
+// Whose move it is, relative to the player whose point of view an Anfield takes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub enum Player {
+    Own,
+    Opponent,
+}
+
+impl Player {
+    pub fn other(self) -> Self {
+        match self {
+            Player::Own => Player::Opponent,
+            Player::Opponent => Player::Own,
+        }
+    }
+
+    pub fn symbol(self) -> CellRole {
+        match self {
+            Player::Own => CellRole::OwnSymbol,
+            Player::Opponent => CellRole::OpponentSymbol,
+        }
+    }
+
+    pub fn latest_move(self) -> CellRole {
+        match self {
+            Player::Own => CellRole::OwnLatestMove,
+            Player::Opponent => CellRole::OpponentLatestMove,
+        }
+    }
+}
+
 pub struct Chars {
     pub own_char: char,
     pub own_latest_char: char,
@@ -0,0 +1,256 @@
+// Who can reach what. Pieces can only be placed on empty cells, so each player's future territory is the empty space it can reach from its own cells without crossing the other's; a breadth-first search from all of a player's cells at once gives how far away each cell is.
+
+use std::{cmp::Ordering, collections::VecDeque};
+
+use crate::{
+    anfield::Anfield,
+    piece::Piece,
+    symbols::{CellRole, Player},
+};
+
+pub const UNREACHABLE: usize = usize::MAX;
+
+// Cells whose index in `Anfield::cells` is next to `index`, horizontally or vertically.
+pub fn neighbors(anfield: &Anfield, index: usize) -> impl Iterator<Item = usize> {
+    neighbor_slots(anfield, index).into_iter().flatten()
+}
+
+fn neighbor_slots(anfield: &Anfield, index: usize) -> [Option<usize>; 4] {
+    let (width, height) = (anfield.width, anfield.height);
+    let (x, y) = (index % width, index / width);
+    [
+        (x > 0).then(|| index - 1),
+        (x + 1 < width).then(|| index + 1),
+        (y > 0).then(|| index - width),
+        (y + 1 < height).then(|| index + width),
+    ]
+}
+
+pub fn belongs_to(anfield: &Anfield, index: usize, player: Player) -> bool {
+    let role = anfield.cells[index];
+    role == player.symbol() || role == player.latest_move()
+}
+
+// Steps from the nearest of the player's cells to each cell, moving through empty cells only. The player's own cells are 0 away, and the other player's cells, like empty cells walled off from the player, are `UNREACHABLE`.
+pub fn distances(anfield: &Anfield, player: Player) -> Vec<usize> {
+    let sources: Vec<usize> = (0..anfield.cells.len())
+        .filter(|&index| belongs_to(anfield, index, player))
+        .collect();
+    distances_from(anfield, &sources)
+}
+
+// The same from any cells.
+pub fn distances_from(anfield: &Anfield, sources: &[usize]) -> Vec<usize> {
+    let mut distance = vec![UNREACHABLE; anfield.cells.len()];
+    let mut queue = VecDeque::new();
+    for &index in sources {
+        if distance[index] != 0 {
+            distance[index] = 0;
+            queue.push_back(index);
+        }
+    }
+
+    while let Some(index) = queue.pop_front() {
+        for next in neighbors(anfield, index) {
+            if distance[next] == UNREACHABLE && anfield.cells[next] == CellRole::Empty {
+                distance[next] = distance[index] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distance
+}
+
+// The empty cells split by who gets there first: a Voronoi partition of the free space. Cells both reach at the same time are contested, and cells neither can reach belong to nobody.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Territory {
+    pub own: usize,
+    pub opponent: usize,
+    pub contested: usize,
+}
+
+impl Territory {
+    pub fn margin(&self) -> isize {
+        self.own as isize - self.opponent as isize
+    }
+}
+
+pub fn territory(anfield: &Anfield) -> Territory {
+    let own = distances(anfield, Player::Own);
+    let opponent = distances(anfield, Player::Opponent);
+    territory_from(anfield, &own, &opponent)
+}
+
+// The same, from distances already worked out.
+pub fn territory_from(anfield: &Anfield, own: &[usize], opponent: &[usize]) -> Territory {
+    let mut territory = Territory::default();
+    for ((role, own), opponent) in anfield.cells.iter().zip(own).zip(opponent) {
+        if *role != CellRole::Empty {
+            continue;
+        }
+        match own.cmp(opponent) {
+            Ordering::Less => territory.own += 1,
+            Ordering::Greater => territory.opponent += 1,
+            Ordering::Equal if *own != UNREACHABLE => territory.contested += 1,
+            Ordering::Equal => (),
+        }
+    }
+    territory
+}
+
+// Whether no empty cell is left that both players can reach, so that each has only its own space to fill.
+pub fn separated(anfield: &Anfield) -> bool {
+    let own = distances(anfield, Player::Own);
+    let opponent = distances(anfield, Player::Opponent);
+    !anfield
+        .cells
+        .iter()
+        .zip(own)
+        .zip(opponent)
+        .any(|((role, own), opponent)| {
+            *role == CellRole::Empty && own != UNREACHABLE && opponent != UNREACHABLE
+        })
+}
+
+// How many empty cells each player can reach at all, own first. Cells both can reach count for both; a player's reach only shrinks when its way to some cells is cut off.
+pub fn reach(own: &[usize], opponent: &[usize], anfield: &Anfield) -> [usize; 2] {
+    let mut reach = [0, 0];
+    for ((role, own), opponent) in anfield.cells.iter().zip(own).zip(opponent) {
+        if *role == CellRole::Empty {
+            reach[0] += usize::from(*own != UNREACHABLE);
+            reach[1] += usize::from(*opponent != UNREACHABLE);
+        }
+    }
+    reach
+}
+
+// The sum over the piece's cells of how far each is from `distance`'s sources, e.g. the opponent. Each is capped at the board's span, since a walled-off opponent is `UNREACHABLE`.
+pub fn approach(anfield: &Anfield, piece: &Piece, [x, y]: [i32; 2], distance: &[usize]) -> usize {
+    piece
+        .shape
+        .iter()
+        .map(|cell| {
+            let index = (y + cell.y as i32) as usize * anfield.width + (x + cell.x as i32) as usize;
+            distance[index].min(anfield.width + anfield.height)
+        })
+        .sum()
+}
+
+// Empty cells whose removal would split the empty space around them in two: the narrowest points of corridors. This is Tarjan's algorithm, done with an explicit stack, since a board can have ten thousand cells.
+pub fn articulation_points(anfield: &Anfield) -> Vec<usize> {
+    let empty = |index: usize| anfield.cells[index] == CellRole::Empty;
+    let count = anfield.cells.len();
+    // Order of discovery, from 1; 0 is not yet visited.
+    let mut order = vec![0; count];
+    let mut low = vec![0; count];
+    let mut is_cut = vec![false; count];
+    let mut time = 0;
+
+    for root in 0..count {
+        if !empty(root) || order[root] != 0 {
+            continue;
+        }
+        time += 1;
+        order[root] = time;
+        low[root] = time;
+        let mut root_children = 0;
+        // Each cell on the path from the root, its parent, and which of its neighbors to look at next.
+        let mut stack = vec![(root, usize::MAX, 0)];
+
+        while let Some(&(cell, parent, slot)) = stack.last() {
+            if slot < 4 {
+                stack.last_mut().expect("Non-empty stack").2 += 1;
+                let Some(next) = neighbor_slots(anfield, cell)[slot].filter(|&n| empty(n)) else {
+                    continue;
+                };
+                if order[next] == 0 {
+                    time += 1;
+                    order[next] = time;
+                    low[next] = time;
+                    if cell == root {
+                        root_children += 1;
+                    }
+                    stack.push((next, cell, 0));
+                } else if next != parent {
+                    low[cell] = low[cell].min(order[next]);
+                }
+            } else {
+                stack.pop();
+                if let Some(&(up, ..)) = stack.last() {
+                    low[up] = low[up].min(low[cell]);
+                    if up != root && low[cell] >= order[up] {
+                        is_cut[up] = true;
+                    }
+                }
+            }
+        }
+
+        if root_children > 1 {
+            is_cut[root] = true;
+        }
+    }
+
+    (0..count).filter(|&index| is_cut[index]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map;
+
+    #[test]
+    fn test_distances_go_around_the_opponent() {
+        let anfield = map::parse("@$..\n.$..\n....", 1).expect("Valid map");
+        let own = distances(&anfield, Player::Own);
+        assert_eq!(own[0], 0);
+        assert_eq!(own[1], UNREACHABLE);
+        assert_eq!(own[2], 6);
+        assert_eq!(own[4], 1);
+    }
+
+    #[test]
+    fn test_territory() {
+        // The wall of `$` shuts player 1 into the left-hand column, where the opponent is just as close to the middle cell and closer to the bottom one.
+        let anfield = map::parse("@$..\n.$..\n.$..", 1).expect("Valid map");
+        assert_eq!(
+            territory(&anfield),
+            Territory {
+                own: 0,
+                opponent: 7,
+                contested: 1
+            }
+        );
+
+        let anfield = map::parse("@...$", 1).expect("Valid map");
+        let split = territory(&anfield);
+        assert_eq!((split.own, split.opponent, split.contested), (1, 1, 1));
+        assert_eq!(split.margin(), 0);
+    }
+
+    #[test]
+    fn test_articulation_points() {
+        // Two rooms joined by a doorway at (3, 1). The cells either side of it are cut points too, since the doorway is only reached through them, and so is (5, 3), the way into the bottom-right corner.
+        let anfield = map::parse("@..$...\n.......\n...$..$\n...$...", 1).expect("Valid map");
+        assert_eq!(articulation_points(&anfield), vec![9, 10, 11, 26]);
+
+        // Along a one-cell-wide corridor, every cell but the ends is a cut point.
+        let corridor = map::parse("@....$", 1).expect("Valid map");
+        assert_eq!(articulation_points(&corridor), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_separated() {
+        assert!(!separated(&map::parse("@...$", 1).expect("Valid map")));
+        assert!(separated(&map::parse("..@$..", 1).expect("Valid map")));
+        assert!(separated(&map::parse("@.@$\n..@$", 1).expect("Valid map")));
+    }
+
+    #[test]
+    fn test_reach() {
+        let anfield = map::parse("@.$..", 1).expect("Valid map");
+        let own = distances(&anfield, Player::Own);
+        let opponent = distances(&anfield, Player::Opponent);
+        assert_eq!(reach(&own, &opponent, &anfield), [1, 3]);
+    }
+}
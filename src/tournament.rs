@@ -0,0 +1,157 @@
+use std::fmt;
+
+use crate::scoring::GameResult;
+
+// One game of a round robin. `players` are indices into the list of bots, player 1 first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pairing {
+    pub players: [usize; 2],
+    pub map: usize,
+    pub seed: u64,
+}
+
+// Every pair of bots on every map with every seed, each pair playing twice per seed, once from each seat, as in the audit.
+pub fn schedule(bots: usize, maps: usize, seeds: &[u64]) -> Vec<Pairing> {
+    let mut pairings = Vec::new();
+    for map in 0..maps {
+        for a in 0..bots {
+            for b in a + 1..bots {
+                for &seed in seeds {
+                    for players in [[a, b], [b, a]] {
+                        pairings.push(Pairing { players, map, seed });
+                    }
+                }
+            }
+        }
+    }
+    pairings
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Standings {
+    pub title: String,
+    pub names: Vec<String>,
+    pub records: Vec<Record>,
+}
+
+impl Standings {
+    pub fn new(title: &str, names: &[String]) -> Self {
+        Standings {
+            title: title.to_string(),
+            names: names.to_vec(),
+            records: vec![Record::default(); names.len()],
+        }
+    }
+
+    // A game that couldn't be played because of the player in `seat`, 0 or 1, counts as its loss.
+    pub fn record_forfeit(&mut self, players: [usize; 2], seat: usize) {
+        self.records[players[seat]].losses += 1;
+        self.records[players[1 - seat]].wins += 1;
+    }
+
+    pub fn record(&mut self, players: [usize; 2], result: &GameResult) {
+        match result.winner() {
+            Some(id) => {
+                let winner = players[id as usize - 1];
+                let loser = players[2 - id as usize];
+                self.records[winner].wins += 1;
+                self.records[loser].losses += 1;
+            }
+            None => {
+                self.records[players[0]].draws += 1;
+                self.records[players[1]].draws += 1;
+            }
+        }
+    }
+}
+
+// Best first, by wins and then draws.
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.names.iter().map(String::len).max().unwrap_or(0).max(3);
+        let mut order: Vec<usize> = (0..self.names.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse((self.records[i].wins, self.records[i].draws)));
+
+        writeln!(f, "{}", self.title)?;
+        writeln!(f, "{:width$} {:>5} {:>5} {:>5}", "bot", "W", "D", "L")?;
+        for i in order {
+            let Record {
+                wins,
+                draws,
+                losses,
+            } = self.records[i];
+            writeln!(
+                f,
+                "{:width$} {:>5} {:>5} {:>5}",
+                self.names[i], wins, draws, losses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_plays_both_seats() {
+        let pairings = schedule(3, 2, &[7, 8]);
+        assert_eq!(pairings.len(), 3 * 2 * 2 * 2);
+        assert!(pairings.contains(&Pairing {
+            players: [0, 2],
+            map: 1,
+            seed: 8
+        }));
+        assert!(pairings.contains(&Pairing {
+            players: [2, 0],
+            map: 1,
+            seed: 8
+        }));
+        assert!(pairings.iter().all(|p| p.players[0] != p.players[1]));
+    }
+
+    #[test]
+    fn test_standings() {
+        let names = vec!["a".to_string(), "b".to_string()];
+        let mut standings = Standings::new("map00", &names);
+        let result = |scores| GameResult {
+            scores,
+            stuck_turns: [None, None],
+            turns: 0,
+        };
+        standings.record([0, 1], &result([5, 3]));
+        standings.record([1, 0], &result([5, 3]));
+        standings.record([1, 0], &result([4, 4]));
+        standings.record_forfeit([0, 1], 1);
+
+        assert_eq!(
+            standings.records[0],
+            Record {
+                wins: 2,
+                draws: 1,
+                losses: 1
+            }
+        );
+        assert_eq!(
+            standings.records[1],
+            Record {
+                wins: 1,
+                draws: 1,
+                losses: 2
+            }
+        );
+        assert!(
+            standings
+                .to_string()
+                .starts_with("map00\nbot     W     D     L\n")
+        );
+    }
+}
@@ -0,0 +1,184 @@
+// A record of what a bot saw and did. The format is the engine's own protocol, starting with the `$$$ exec` line and then, for each turn, the Anfield and Piece as received, followed by a line with the bot's answer and how long it took to decide:
+//
+// -> 7 2 1534us
+//
+// Since the boards are in the usual format, a transcript can be piped straight into the visualizer.
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::{anfield::Anfield, errors::GameError, parse, piece::Piece, protocol};
+
+const MOVE_PREFIX: &str = "-> ";
+
+pub struct Recorder {
+    out: Box<dyn Write>,
+}
+
+impl Recorder {
+    // `first_line` is the `$$$ exec` line the bot was given, so that a transcript says which player it was.
+    pub fn create(path: &Path, first_line: &str) -> io::Result<Self> {
+        let mut out = BufWriter::new(File::create(path)?);
+        writeln!(out, "{}", first_line)?;
+        Ok(Recorder { out: Box::new(out) })
+    }
+
+    pub fn record(
+        &mut self,
+        anfield: &Anfield,
+        piece: &Piece,
+        chosen: [i32; 2],
+        elapsed: Duration,
+    ) -> io::Result<()> {
+        let mut text = String::new();
+        protocol::write_anfield(&mut text, anfield);
+        protocol::write_piece(&mut text, piece);
+        self.out.write_all(text.as_bytes())?;
+        writeln!(
+            self.out,
+            "{}{} {} {}us",
+            MOVE_PREFIX,
+            chosen[0],
+            chosen[1],
+            elapsed.as_micros()
+        )?;
+        // Flush every turn, since the bot may be killed at any time once the game is over.
+        self.out.flush()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Turn {
+    pub anfield: Anfield,
+    pub piece: Piece,
+    pub chosen: [i32; 2],
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    pub own_id: u8,
+    pub turns: Vec<Turn>,
+}
+
+pub fn load(path: &Path) -> Result<Transcript, GameError> {
+    let file = File::open(path)?;
+    parse(&mut BufReader::new(file).lines())
+}
+
+pub fn parse(
+    lines: &mut impl Iterator<Item = Result<String, io::Error>>,
+) -> Result<Transcript, GameError> {
+    let first_line = parse::read_line(lines, "first")?;
+    let own_id = parse::get_ids(first_line)?;
+
+    let mut turns = Vec::new();
+    while let Some(header) = lines.next() {
+        let header = header?;
+        if header.trim().is_empty() {
+            continue;
+        }
+
+        let [width, height] = parse::get_width_and_height(header.clone())
+            .map_err(|_| GameError::ParseAnfieldDimensions(header))?;
+        let mut anfield = Anfield::new(own_id);
+        anfield.set_dimensions(width, height);
+        anfield
+            .parse(lines)
+            .map_err(|e| GameError::ParseAnfieldBody(e.to_string()))?;
+
+        let piece_header = parse::read_line(lines, "piece header")?;
+        let [width, height] = parse::get_width_and_height(piece_header.clone())
+            .map_err(|_| GameError::ParsePieceDimensions(piece_header))?;
+        let piece = Piece::new(lines, width, height)
+            .map_err(|e| GameError::ParsePieceBody(e.to_string()))?;
+
+        let move_line = parse::read_line(lines, "move")?;
+        let (chosen, elapsed) = parse_move_line(&move_line)
+            .ok_or_else(|| GameError::ParseTranscript(move_line.clone()))?;
+
+        turns.push(Turn {
+            anfield,
+            piece,
+            chosen,
+            elapsed,
+        });
+    }
+
+    Ok(Transcript { own_id, turns })
+}
+
+fn parse_move_line(line: &str) -> Option<([i32; 2], Duration)> {
+    let rest = line.strip_prefix(MOVE_PREFIX)?;
+    let (answer, time) = rest.rsplit_once(' ')?;
+    let chosen = protocol::parse_move(answer)?;
+    let micros = time.strip_suffix("us")?.parse().ok()?;
+    Some((chosen, Duration::from_micros(micros)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cell::Cell,
+        map, rules,
+        strategy::{Strategy, attack::Attack},
+        symbols::{CellRole, Player},
+    };
+
+    #[test]
+    fn test_record_and_load() {
+        let path = std::env::temp_dir().join(format!("filler-transcript-{}", std::process::id()));
+        let mut anfield =
+            map::parse("@.........\n..........\n.........$\n", 2).expect("Failed to parse map");
+        let piece = Piece {
+            width: 2,
+            height: 1,
+            shape: vec![Cell { x: 0, y: 0 }, Cell { x: 1, y: 0 }],
+        };
+
+        let mut recorder =
+            Recorder::create(&path, "$$$ exec p2 : [maximilian]").expect("Failed to create");
+        let mut chosen = Vec::new();
+        for turn in 0..2 {
+            let mv = Attack::default().choose_move(&anfield, &piece);
+            recorder
+                .record(&anfield, &piece, mv, Duration::from_micros(100 + turn))
+                .expect("Failed to record");
+            assert!(rules::check_placement(&anfield, &piece, mv[0], mv[1]).is_ok());
+            anfield.apply(&piece, mv[0], mv[1], Player::Own);
+            chosen.push(mv);
+        }
+        drop(recorder);
+
+        let transcript = load(&path).expect("Failed to load transcript");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(transcript.own_id, 2);
+        assert_eq!(transcript.turns.len(), 2);
+        assert_eq!(transcript.turns[1].chosen, chosen[1]);
+        assert_eq!(transcript.turns[1].elapsed, Duration::from_micros(101));
+        assert_eq!(transcript.turns[1].anfield.cells.len(), 30);
+        assert_eq!(
+            transcript.turns[1]
+                .anfield
+                .get_cell_role(chosen[0][0] as usize, chosen[0][1] as usize),
+            Some(CellRole::OwnLatestMove)
+        );
+        assert_eq!(transcript.turns[0].piece.shape, piece.shape);
+    }
+
+    #[test]
+    fn test_parse_move_line() {
+        assert_eq!(
+            parse_move_line("-> -1 4 250us"),
+            Some(([-1, 4], Duration::from_micros(250)))
+        );
+        assert_eq!(parse_move_line("-> 1 4"), None);
+        assert_eq!(parse_move_line("1 4 250us"), None);
+    }
+}
@@ -0,0 +1,285 @@
+// Searches Attack's parameters with a simple genetic algorithm. Every candidate in a generation plays the same games, on the same maps and seeds and from both seats, against Attack with its default parameters, and its fitness is its average score (1 for a win, 0.5 for a draw). The fittest few survive unchanged, but each generation plays a fresh set of seeds, so a candidate that was only lucky doesn't stay on top for long.
+//
+// Fitness on fresh seeds can't be compared from one generation to the next, so each generation's leader also plays a fixed set of seeds that the search never selects on. The best so far is the one that does best there, starting from the defaults themselves. The opponent is always the defaults rather than the other candidates, since those are what a tuned file replaces, and so that those scores mean the same thing in every generation.
+
+use crate::{
+    anfield::Anfield,
+    batch,
+    rng::Rng,
+    scoring::GameResult,
+    simulate::simulate,
+    strategy::attack::{Attack, params::Params},
+};
+
+// The ranges searched. Only the ratio of `distance_weight` to `centre_bonus` matters, but letting both vary keeps the search simple.
+const DISTANCE_WEIGHT: [f64; 2] = [0.0, 4.0];
+const BORDER_WIDTH: [usize; 2] = [0, 4];
+const BORDER_COST: [f64; 2] = [0.0, 8.0];
+const CENTRE_BONUS: [f64; 2] = [0.0, 20.0];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    pub population: usize,
+    pub generations: usize,
+    // Games per map and seat that each candidate plays in each generation.
+    pub seeds: usize,
+    pub seed: u64,
+    pub threads: usize,
+    pub show_progress: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Generation {
+    pub number: usize,
+    // The best parameters so far, and their fitness on the fixed seeds.
+    pub best: Params,
+    pub fitness: f64,
+    // Whether this generation found them.
+    pub improved: bool,
+}
+
+// One game the search played, for exporting (see `export`). `map` is an index into the maps given to `tune`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Played {
+    // By seat, player 1 first.
+    pub players: [Params; 2],
+    pub map: usize,
+    pub seed: u64,
+    pub result: GameResult,
+}
+
+// `maps` are starting boards as seen by player 1. `report` is called after each generation with the best parameters so far, which are also returned at the end, and `played` with every game, in a fixed order.
+pub fn tune(
+    maps: &[Anfield],
+    options: &Options,
+    mut report: impl FnMut(&Generation),
+    mut played: impl FnMut(&Played),
+) -> Generation {
+    assert!(!maps.is_empty(), "Tuning needs at least one map");
+    let size = options.population.max(2);
+    let mut rng = Rng::new(options.seed);
+
+    let mut population = vec![Params::default()];
+    while population.len() < size {
+        population.push(random_params(&mut rng));
+    }
+
+    // Counting down from the seed, where the seeds for selection count up from it.
+    let fixed_seeds: Vec<u64> = (1..=options.seeds.max(1) as u64)
+        .map(|i| options.seed.wrapping_sub(i))
+        .collect();
+    let mut best = Generation {
+        number: 0,
+        best: Params::default(),
+        fitness: evaluate(
+            &[Params::default()],
+            maps,
+            &fixed_seeds,
+            options,
+            &mut played,
+        )[0],
+        improved: false,
+    };
+
+    for number in 0..options.generations.max(1) {
+        let first_seed = options
+            .seed
+            .wrapping_add((number * options.seeds.max(1)) as u64);
+        let seeds: Vec<u64> = (0..options.seeds.max(1) as u64)
+            .map(|i| first_seed.wrapping_add(i))
+            .collect();
+        let fitness = evaluate(&population, maps, &seeds, options, &mut played);
+
+        let mut ranked: Vec<usize> = (0..population.len()).collect();
+        ranked.sort_by(|&a, &b| fitness[b].total_cmp(&fitness[a]));
+        let leader = population[ranked[0]];
+        let leader_fitness = evaluate(&[leader], maps, &fixed_seeds, options, &mut played)[0];
+        let improved = leader_fitness > best.fitness;
+        if improved {
+            best.best = leader;
+            best.fitness = leader_fitness;
+        }
+        best.number = number;
+        best.improved = improved;
+        report(&best);
+
+        let elites = (size / 4).max(1);
+        let mut next: Vec<Params> = ranked[..elites].iter().map(|&i| population[i]).collect();
+        while next.len() < size {
+            let a = select(&fitness, &mut rng);
+            let b = select(&fitness, &mut rng);
+            let child = crossover(&population[a], &population[b], &mut rng);
+            next.push(mutate(&child, &mut rng));
+        }
+        population = next;
+    }
+
+    best
+}
+
+fn evaluate(
+    population: &[Params],
+    maps: &[Anfield],
+    seeds: &[u64],
+    options: &Options,
+    played: &mut impl FnMut(&Played),
+) -> Vec<f64> {
+    let mut jobs = Vec::new();
+    for candidate in 0..population.len() {
+        for map in 0..maps.len() {
+            for &seed in seeds {
+                for seat in 0..2 {
+                    jobs.push((candidate, map, seed, seat));
+                }
+            }
+        }
+    }
+
+    let baseline = Params::default();
+    let results = batch::run(
+        &jobs,
+        options.threads,
+        options.show_progress,
+        |&(candidate, map, seed, seat)| {
+            let candidate = Attack::new(population[candidate]);
+            let baseline = Attack::new(baseline);
+            if seat == 0 {
+                simulate(&candidate, &baseline, &maps[map], seed)
+            } else {
+                simulate(&baseline, &candidate, &maps[map], seed)
+            }
+        },
+    );
+
+    let mut fitness = vec![0.0; population.len()];
+    for (&(candidate, map, seed, seat), result) in jobs.iter().zip(results) {
+        fitness[candidate] += match result.winner() {
+            Some(winner) if winner as usize == seat + 1 => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        let mut players = [population[candidate], baseline];
+        if seat == 1 {
+            players.swap(0, 1);
+        }
+        played(&Played {
+            players,
+            map,
+            seed,
+            result,
+        });
+    }
+    let games = (maps.len() * seeds.len() * 2) as f64;
+    fitness.iter().map(|total| total / games).collect()
+}
+
+fn random_params(rng: &mut Rng) -> Params {
+    let uniform = |rng: &mut Rng, [low, high]: [f64; 2]| low + rng.next_f64() * (high - low);
+    Params {
+        distance_weight: uniform(rng, DISTANCE_WEIGHT),
+        border_width: rng.range(BORDER_WIDTH[0], BORDER_WIDTH[1]),
+        border_cost: uniform(rng, BORDER_COST),
+        centre_bonus: uniform(rng, CENTRE_BONUS),
+    }
+}
+
+// The better of two candidates picked at random.
+fn select(fitness: &[f64], rng: &mut Rng) -> usize {
+    let a = rng.below(fitness.len());
+    let b = rng.below(fitness.len());
+    if fitness[b] > fitness[a] { b } else { a }
+}
+
+// Each parameter from one parent or the other.
+fn crossover(a: &Params, b: &Params, rng: &mut Rng) -> Params {
+    Params {
+        distance_weight: either(a.distance_weight, b.distance_weight, rng),
+        border_width: either(a.border_width, b.border_width, rng),
+        border_cost: either(a.border_cost, b.border_cost, rng),
+        centre_bonus: either(a.centre_bonus, b.centre_bonus, rng),
+    }
+}
+
+fn either<T>(a: T, b: T, rng: &mut Rng) -> T {
+    if rng.below(2) == 0 { a } else { b }
+}
+
+// Nudges about half the parameters, by a tenth of their range on average, keeping them in range.
+fn mutate(params: &Params, rng: &mut Rng) -> Params {
+    let nudge = |rng: &mut Rng, value: f64, [low, high]: [f64; 2]| {
+        if rng.below(2) == 0 {
+            value
+        } else {
+            (value + rng.normal() * (high - low) / 10.0).clamp(low, high)
+        }
+    };
+    let border_width = match rng.below(4) {
+        0 => params.border_width.saturating_sub(1),
+        1 => params.border_width + 1,
+        _ => params.border_width,
+    };
+    Params {
+        distance_weight: nudge(rng, params.distance_weight, DISTANCE_WEIGHT),
+        border_width: border_width.clamp(BORDER_WIDTH[0], BORDER_WIDTH[1]),
+        border_cost: nudge(rng, params.border_cost, BORDER_COST),
+        centre_bonus: nudge(rng, params.centre_bonus, CENTRE_BONUS),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map::generator::{self, MapOptions};
+
+    fn in_range(params: &Params) -> bool {
+        let within = |value: f64, [low, high]: [f64; 2]| (low..=high).contains(&value);
+        within(params.distance_weight, DISTANCE_WEIGHT)
+            && (BORDER_WIDTH[0]..=BORDER_WIDTH[1]).contains(&params.border_width)
+            && within(params.border_cost, BORDER_COST)
+            && within(params.centre_bonus, CENTRE_BONUS)
+    }
+
+    #[test]
+    fn test_mutation_stays_in_range() {
+        let mut rng = Rng::new(3);
+        let mut params = random_params(&mut rng);
+        for _ in 0..1000 {
+            params = mutate(&params, &mut rng);
+            assert!(in_range(&params), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn test_tune_is_deterministic() {
+        let map = generator::generate(&MapOptions::new(14, 10), 1, 5);
+        let options = Options {
+            population: 4,
+            generations: 2,
+            seeds: 1,
+            seed: 11,
+            threads: 2,
+            show_progress: false,
+        };
+
+        let mut reports = Vec::new();
+        let mut games = 0;
+        let a = tune(
+            std::slice::from_ref(&map),
+            &options,
+            |g| reports.push(*g),
+            |_| games += 1,
+        );
+        let b = tune(std::slice::from_ref(&map), &options, |_| (), |_| ());
+
+        // The defaults on the fixed seeds, then each generation's population and leader, from both seats.
+        assert_eq!(games, 2 + 2 * (4 + 1) * 2);
+        let numbers: Vec<usize> = reports.iter().map(|g| g.number).collect();
+        assert_eq!(numbers, vec![0, 1]);
+        // The best so far never gets worse.
+        assert!(reports[1].fitness >= reports[0].fitness);
+        assert_eq!(a, reports[1]);
+        assert_eq!(a, b);
+        assert!(in_range(&a.best));
+        assert!((0.0..=1.0).contains(&a.fitness));
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    time::Duration,
+};
+
+use filler::engine::{self, Config, StuckReason};
+
+fn write_map(name: &str, rows: &[&str]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("filler-{}-{}", name, std::process::id()));
+    fs::write(&path, rows.join("\n")).expect("Failed to write map");
+    path
+}
+
+fn run_engine(map: &Path, seed: &str, quiet: bool) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_filler-engine"));
+    command.args(["-f", map.to_str().unwrap()]);
+    command.args(["-p1", env!("CARGO_BIN_EXE_maximilian")]);
+    command.args(["-p2", env!("CARGO_BIN_EXE_maximilian")]);
+    command.args(["-s", seed]);
+    if quiet {
+        command.arg("-q");
+    }
+    command.output().expect("Failed to run filler-engine")
+}
+
+fn final_scores(stdout: &str) -> Vec<usize> {
+    stdout
+        .lines()
+        .filter(|line| line.starts_with("== "))
+        .map(|line| {
+            line.rsplit(' ')
+                .next()
+                .and_then(|n| n.parse().ok())
+                .expect("Score should be a number")
+        })
+        .collect()
+}
+
+#[test]
+fn test_engine_plays_maximilian_against_itself() {
+    let map = write_map(
+        "engine-self-play",
+        &[
+            "....................",
+            "....................",
+            "...@................",
+            "....................",
+            "....................",
+            "....................",
+            "....................",
+            "....................",
+            "....................",
+            "....................",
+            "....................",
+            "....................",
+            "................$...",
+            "....................",
+            "....................",
+        ],
+    );
+
+    let output = run_engine(&map, "12345", false);
+    assert!(output.status.success(), "Engine failed: {:?}", output);
+    let stdout = String::from_utf8(output.stdout).expect("Invalid UTF-8");
+
+    assert!(stdout.starts_with("$$$ exec p1 : ["));
+    assert!(stdout.contains("Anfield 20 15:"));
+    assert!(stdout.contains("Piece "));
+
+    let scores = final_scores(&stdout);
+    assert_eq!(scores.len(), 2, "Expected two final score lines");
+    assert!(scores.iter().all(|&s| s > 0), "Scores: {:?}", scores);
+
+    // The same seed should give the same game.
+    let again = run_engine(&map, "12345", true);
+    let again = String::from_utf8(again.stdout).expect("Invalid UTF-8");
+    assert_eq!(final_scores(&again), scores);
+    assert!(
+        !again.contains("Anfield"),
+        "Quiet mode should only print scores"
+    );
+
+    let _ = fs::remove_file(map);
+}
+
+#[test]
+fn test_engine_rejects_missing_map() {
+    let map = PathBuf::from("/nonexistent/filler/map");
+    let output = run_engine(&map, "1", true);
+    assert!(!output.status.success());
+}
+
+fn write_bot(name: &str, script: &str) -> PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = std::env::temp_dir().join(format!("filler-bot-{}-{}", name, std::process::id()));
+    fs::write(&path, format!("#!/bin/sh\n{}\n", script)).expect("Failed to write bot");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("Failed to chmod bot");
+    path
+}
+
+#[test]
+fn test_engine_reports_why_each_bot_got_stuck() {
+    let map = write_map("engine-stuck", &["@.........", "..........", ".........$"]);
+    let cases = [
+        ("sleepy", "sleep 5", "Timeout"),
+        (
+            "chatty",
+            "while read line; do echo hello; done",
+            "Malformed(\"hello\")",
+        ),
+        ("quitter", "exit 0", "Exited"),
+    ];
+
+    for (name, script, expected) in cases {
+        let bot = write_bot(name, script);
+        let config = Config {
+            map: map.clone(),
+            players: [PathBuf::from(env!("CARGO_BIN_EXE_maximilian")), bot.clone()],
+            seed: 3,
+            pieces: None,
+            timeout: Duration::from_millis(300),
+            quiet: true,
+        };
+
+        let report = engine::run(&config, &mut io::sink()).expect("Engine failed");
+        let stuck = report.stuck[1].as_ref().expect("Bot should be stuck");
+        assert!(
+            format!("{:?}", stuck.reason).starts_with(expected),
+            "{}: unexpected reason {:?}",
+            name,
+            stuck.reason
+        );
+        assert_eq!(stuck.turn, 2);
+        assert!(stuck.could_move);
+        assert_eq!(report.result.scores[1], 0);
+        assert_eq!(report.result.winner(), Some(1));
+        let own = report.stuck[0]
+            .as_ref()
+            .expect("Maximilian should be stuck");
+        assert!(matches!(own.reason, StuckReason::Illegal(_)));
+        assert!(!own.could_move);
+        let _ = fs::remove_file(bot);
+    }
+
+    let _ = fs::remove_file(map);
+}
+
+#[test]
+fn test_engine_captures_a_panicking_bot() {
+    let map = write_map("engine-panic", &["@.........", "..........", ".........$"]);
+    let bot = write_bot(
+        "panicky",
+        "read line\necho \"thread 'main' panicked at src/anfield.rs:40:14:\" >&2\necho 'Invalid cell' >&2\nexit 101",
+    );
+    let config = Config {
+        map: map.clone(),
+        players: [bot.clone(), PathBuf::from(env!("CARGO_BIN_EXE_maximilian"))],
+        seed: 3,
+        pieces: None,
+        timeout: Duration::from_secs(5),
+        quiet: true,
+    };
+
+    let report = engine::run(&config, &mut io::sink()).expect("Engine failed");
+    let _ = fs::remove_file(bot);
+    let _ = fs::remove_file(map);
+
+    let stuck = report.stuck[0].as_ref().expect("Bot should be stuck");
+    assert_eq!(stuck.turn, 1);
+    let StuckReason::Exited(exit) = &stuck.reason else {
+        panic!("Unexpected reason {:?}", stuck.reason);
+    };
+    assert_eq!(exit.status.and_then(|s| s.code()), Some(101));
+    assert_eq!(
+        exit.panic.as_deref(),
+        Some("Invalid cell at src/anfield.rs:40:14")
+    );
+    assert!(report.stderr[0].contains("Invalid cell"));
+    assert!(
+        stuck.to_string().contains("after panicking: Invalid cell"),
+        "{}",
+        stuck
+    );
+}
@@ -76,7 +76,7 @@ O
 "#;
 
     let mut child = Command::new("cargo")
-        .args(&["run", "--bin", "maximilian"])
+        .args(["run", "--bin", "maximilian"])
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .spawn()
@@ -97,7 +97,7 @@ O
     let output_str = String::from_utf8(output.stdout).expect("Invalid UTF-8");
 
     for line in output_str.lines() {
-        let parts: Vec<&str> = line.trim().split_whitespace().collect();
+        let parts: Vec<&str> = line.split_whitespace().collect();
         assert_eq!(
             parts.len(),
             2,